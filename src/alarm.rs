@@ -0,0 +1,262 @@
+use core::ops::BitOr;
+
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{
+    BIT_ALARM_AE, BIT_REGISTER_CONTROL_AIE, BIT_REGISTER_EXTENSION_WADA, BIT_REGISTER_FLAG_AF,
+    REGISTER_ALARM_MIN, REGISTER_CONTROL, REGISTER_EXTENSION, REGISTER_FLAG,
+};
+use crate::{bcd2bin, bin2bcd, Error, Rx8010sj, Weekday};
+
+/// Set of days of the week for weekly alarms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    pub const SUNDAY: Weekdays = Weekdays(0x01);
+    pub const MONDAY: Weekdays = Weekdays(0x02);
+    pub const TUESDAY: Weekdays = Weekdays(0x04);
+    pub const WEDNESDAY: Weekdays = Weekdays(0x08);
+    pub const THURSDAY: Weekdays = Weekdays(0x10);
+    pub const FRIDAY: Weekdays = Weekdays(0x20);
+    pub const SATURDAY: Weekdays = Weekdays(0x40);
+
+    /// Builds a set from the raw WEEK bitmask (bit 0 is Sunday)
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        if bits == 0 || bits > 0x7F {
+            None
+        } else {
+            Some(Weekdays(bits))
+        }
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(self, other: Weekdays) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl From<Weekday> for Weekdays {
+    fn from(weekday: Weekday) -> Self {
+        Weekdays(1 << weekday.number_from_sunday())
+    }
+}
+
+impl BitOr for Weekdays {
+    type Output = Weekdays;
+
+    fn bitor(self, rhs: Weekdays) -> Weekdays {
+        Weekdays(self.0 | rhs.0)
+    }
+}
+
+/// Day selection of the alarm (WADA bit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmDay {
+    /// Every day
+    Any,
+    /// On a day of the month (1-31)
+    DayOfMonth(u8),
+    /// On any of the given days of the week
+    Weekdays(Weekdays),
+}
+
+/// Alarm configuration; a `None` field is ignored when matching ("don't care")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alarm {
+    pub minute: Option<u8>,
+    pub hour: Option<u8>,
+    pub day: AlarmDay,
+}
+
+impl Alarm {
+    /// Encodes the alarm into the MIN/HOUR/WEEK-DAY alarm registers and the
+    /// WADA bit of the Extension register, `None` if a field is out of range.
+    pub(crate) fn to_registers(self) -> Option<([u8; 3], u8)> {
+        let minute = match self.minute {
+            Some(minute) if minute < 60 => bin2bcd(minute),
+            Some(_) => return None,
+            None => BIT_ALARM_AE,
+        };
+        let hour = match self.hour {
+            Some(hour) if hour < 24 => bin2bcd(hour),
+            Some(_) => return None,
+            None => BIT_ALARM_AE,
+        };
+        let (day, wada) = match self.day {
+            AlarmDay::Any => (BIT_ALARM_AE, 0),
+            AlarmDay::DayOfMonth(day) if (1..=31).contains(&day) => {
+                (bin2bcd(day), BIT_REGISTER_EXTENSION_WADA)
+            }
+            AlarmDay::DayOfMonth(_) => return None,
+            AlarmDay::Weekdays(weekdays) => (weekdays.bits(), 0),
+        };
+        Some(([minute, hour, day], wada))
+    }
+
+    /// Decodes the alarm registers and the WADA bit of the Extension
+    /// register, `None` if they hold invalid values
+    pub(crate) fn from_registers(registers: [u8; 3], extension_register: u8) -> Option<Alarm> {
+        let field = |register: u8, max: u8| {
+            if (register & BIT_ALARM_AE) > 0 {
+                Some(None)
+            } else {
+                bcd2bin(register).filter(|value| *value <= max).map(Some)
+            }
+        };
+
+        let minute = field(registers[0], 59)?;
+        let hour = field(registers[1], 23)?;
+        let day = if (registers[2] & BIT_ALARM_AE) > 0 {
+            AlarmDay::Any
+        } else if (extension_register & BIT_REGISTER_EXTENSION_WADA) > 0 {
+            match bcd2bin(registers[2])? {
+                day @ 1..=31 => AlarmDay::DayOfMonth(day),
+                _ => return None,
+            }
+        } else {
+            AlarmDay::Weekdays(Weekdays::from_bits(registers[2])?)
+        };
+
+        Some(Alarm { minute, hour, day })
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Writes the alarm configuration and clears a pending alarm flag.
+    /// The interrupt enable bit is left untouched.
+    pub fn set_alarm(&mut self, alarm: &Alarm) -> Result<(), Error<E>> {
+        let (registers, wada) = alarm.to_registers().ok_or(Error::InvalidArgument)?;
+        self.write_registers(REGISTER_ALARM_MIN, &registers)?;
+        self.modify_register(REGISTER_EXTENSION, BIT_REGISTER_EXTENSION_WADA, wada)?;
+        self.clear_flags(BIT_REGISTER_FLAG_AF)
+    }
+
+    pub fn get_alarm(&mut self) -> Result<Alarm, Error<E>> {
+        let registers = self.read_registers::<3>(REGISTER_ALARM_MIN)?;
+        let extension_register = self.read_register(REGISTER_EXTENSION)?;
+        Alarm::from_registers(registers, extension_register).ok_or(Error::InvalidRegister)
+    }
+
+    /// Enables the alarm interrupt (AIE): /IRQ goes low when the alarm matches.
+    pub fn enable_alarm(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_AIE,
+            BIT_REGISTER_CONTROL_AIE,
+        )
+    }
+
+    /// Disables the alarm interrupt; AF is still raised on a match.
+    pub fn disable_alarm(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(REGISTER_CONTROL, BIT_REGISTER_CONTROL_AIE, 0)
+    }
+
+    /// Reads the alarm flag (AF)
+    pub fn is_alarm_triggered(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG)?;
+        Ok((flag_register & BIT_REGISTER_FLAG_AF) > 0)
+    }
+
+    /// Clears the alarm flag, releasing /IRQ
+    pub fn acknowledge_alarm(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_AF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    #[test]
+    fn registers_round_trip() {
+        let alarms = [
+            Alarm {
+                minute: Some(30),
+                hour: Some(7),
+                day: AlarmDay::Any,
+            },
+            Alarm {
+                minute: None,
+                hour: Some(23),
+                day: AlarmDay::DayOfMonth(31),
+            },
+            Alarm {
+                minute: Some(0),
+                hour: None,
+                day: AlarmDay::Weekdays(Weekdays::MONDAY | Weekdays::FRIDAY),
+            },
+        ];
+        for alarm in alarms {
+            let (registers, wada) = alarm.to_registers().unwrap();
+            assert_eq!(Alarm::from_registers(registers, wada), Some(alarm));
+        }
+    }
+
+    #[test]
+    fn to_registers_rejects_out_of_range() {
+        let alarm = Alarm {
+            minute: None,
+            hour: None,
+            day: AlarmDay::Any,
+        };
+        assert_eq!(
+            Alarm {
+                minute: Some(60),
+                ..alarm
+            }
+            .to_registers(),
+            None
+        );
+        assert_eq!(
+            Alarm {
+                hour: Some(24),
+                ..alarm
+            }
+            .to_registers(),
+            None
+        );
+        assert_eq!(
+            Alarm {
+                day: AlarmDay::DayOfMonth(0),
+                ..alarm
+            }
+            .to_registers(),
+            None
+        );
+    }
+
+    #[test]
+    fn alarm_raises_flag_and_irq() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 29, 30));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let alarm = Alarm {
+            minute: Some(30),
+            hour: Some(7),
+            day: AlarmDay::Any,
+        };
+        rtc.set_alarm(&alarm).unwrap();
+        assert_eq!(rtc.get_alarm(), Ok(alarm));
+        rtc.enable_alarm().unwrap();
+
+        sim.advance(Duration::from_secs(29));
+        assert!(!sim.irq_asserted());
+        assert_eq!(Rx8010sj::new(&mut sim).is_alarm_triggered(), Ok(false));
+
+        sim.advance(Duration::from_secs(1));
+        assert!(sim.irq_asserted());
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.is_alarm_triggered(), Ok(true));
+        rtc.acknowledge_alarm().unwrap();
+        assert!(!sim.irq_asserted());
+    }
+}
@@ -0,0 +1,223 @@
+//! Time setting aligned to an external reference.
+//! Following the datasheet, STOP is set while the calendar is written and
+//! released at the instant the written time becomes true: the sub-second
+//! divider restarts on release, so the next increment comes exactly one
+//! second later.
+
+use core::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use embedded_hal::i2c::I2c;
+
+use crate::{encode_time, DateTime, Error, Rx8010sj};
+
+/// Edge of a synchronization pulse (e.g. a GPS PPS output)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PulseEdge {
+    Rising,
+    Falling,
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Writes the calendar like `set_time`, but leaves the clock stopped;
+    /// `set_stopped(false)` starts it at `date_time`.
+    pub fn set_time_stopped(&mut self, date_time: DateTime) -> Result<(), Error<E>> {
+        let time_registers = encode_time(&date_time).ok_or(Error::UnsupportedDate)?;
+        self.write_time_stopped(&time_registers)?;
+        Ok(())
+    }
+
+    /// Writes the calendar with the clock stopped, then calls `release` and
+    /// restarts the clock as soon as it returns. `release` should block until
+    /// the instant `date_time` refers to.
+    pub fn set_time_on<F: FnOnce()>(
+        &mut self,
+        date_time: DateTime,
+        release: F,
+    ) -> Result<(), Error<E>> {
+        let time_registers = encode_time(&date_time).ok_or(Error::UnsupportedDate)?;
+        let control_register = self.write_time_stopped(&time_registers)?;
+        release();
+        self.release_stop(control_register)
+    }
+
+    /// Writes the calendar with the clock stopped, then polls `pin` for `edge`
+    /// and restarts the clock. `date_time` is the time at that edge, e.g. the
+    /// time announced for the next PPS pulse.
+    /// The edge is only seen after the registers are written: the whole
+    /// sequence must start well within one pulse period.
+    ///
+    /// The pin is read every microsecond, for at most `timeout` in total.
+    /// If the wait fails (`Error::Pin` or `Error::Timeout`), the clock is
+    /// left stopped at `date_time`, VLF untouched and the stored record no
+    /// longer marks the time as trusted: set the time again to recover.
+    pub fn set_time_on_edge<P: InputPin, D: DelayNs>(
+        &mut self,
+        date_time: DateTime,
+        pin: &mut P,
+        edge: PulseEdge,
+        delay: &mut D,
+        timeout: Duration,
+    ) -> Result<(), Error<E>> {
+        let time_registers = encode_time(&date_time).ok_or(Error::UnsupportedDate)?;
+        let control_register = self.stop_and_write_time(&time_registers)?;
+        self.set_time_trusted(false)?;
+        wait_for_edge(pin, edge, delay, timeout)?;
+        self.release_stop(control_register)?;
+        self.time_set()
+    }
+}
+
+/// Interval between two reads of the synchronization pin
+const EDGE_POLL_INTERVAL_US: u32 = 1;
+
+fn wait_for_edge<P: InputPin, D: DelayNs, E>(
+    pin: &mut P,
+    edge: PulseEdge,
+    delay: &mut D,
+    timeout: Duration,
+) -> Result<(), Error<E>> {
+    let active_high = edge == PulseEdge::Rising;
+    let mut polls_left = timeout.as_micros() / EDGE_POLL_INTERVAL_US as u128;
+    let mut wait_for_level = |high: bool| {
+        while pin.is_high().map_err(|_| Error::Pin)? != high {
+            if polls_left == 0 {
+                return Err(Error::Timeout);
+            }
+            polls_left -= 1;
+            delay.delay_us(EDGE_POLL_INTERVAL_US);
+        }
+        Ok(())
+    };
+    // Level before the edge first, so that an already active pulse is skipped
+    wait_for_level(!active_high)?;
+    wait_for_level(active_high)
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::digital::{ErrorKind, ErrorType};
+
+    use super::*;
+    use crate::sim::testing::{date_time, running_at, NoDelay};
+    use crate::{TimeValidity, RECORD_PAYLOAD_SIZE};
+
+    /// Replays `levels`, then stays at the last one
+    struct ScriptedPin {
+        levels: &'static [bool],
+        reads: usize,
+    }
+
+    impl ScriptedPin {
+        fn new(levels: &'static [bool]) -> Self {
+            ScriptedPin { levels, reads: 0 }
+        }
+    }
+
+    impl ErrorType for ScriptedPin {
+        type Error = ErrorKind;
+    }
+
+    impl InputPin for ScriptedPin {
+        fn is_high(&mut self) -> Result<bool, ErrorKind> {
+            let level = self.levels[self.reads.min(self.levels.len() - 1)];
+            self.reads += 1;
+            Ok(level)
+        }
+
+        fn is_low(&mut self) -> Result<bool, ErrorKind> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    struct FailingPin;
+
+    impl ErrorType for FailingPin {
+        type Error = ErrorKind;
+    }
+
+    impl InputPin for FailingPin {
+        fn is_high(&mut self) -> Result<bool, ErrorKind> {
+            Err(ErrorKind::Other)
+        }
+
+        fn is_low(&mut self) -> Result<bool, ErrorKind> {
+            Err(ErrorKind::Other)
+        }
+    }
+
+    #[test]
+    fn set_time_on_edge_releases_stop_after_the_edge() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.store_record(1, &[0; RECORD_PAYLOAD_SIZE]).unwrap();
+
+        // An already high line is skipped, the edge is the second low-high
+        let mut pin = ScriptedPin::new(&[true, false, false, true]);
+        let time = date_time(2024, 6, 1, 12, 0, 0);
+        rtc.set_time_on_edge(
+            time,
+            &mut pin,
+            PulseEdge::Rising,
+            &mut NoDelay,
+            Duration::from_millis(1),
+        )
+        .unwrap();
+        assert_eq!(pin.reads, 4);
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Valid));
+        assert_eq!(rtc.is_time_trusted(), Ok(true));
+        assert_eq!(rtc.get_time(), Ok(time));
+    }
+
+    #[test]
+    fn set_time_on_edge_pin_error_leaves_the_clock_stopped() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.store_record(1, &[0; RECORD_PAYLOAD_SIZE]).unwrap();
+
+        let time = date_time(2024, 6, 1, 12, 0, 0);
+        assert_eq!(
+            rtc.set_time_on_edge(
+                time,
+                &mut FailingPin,
+                PulseEdge::Falling,
+                &mut NoDelay,
+                Duration::from_millis(1),
+            ),
+            Err(Error::Pin)
+        );
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Stopped));
+
+        // Restarting by hand does not bring the trusted marker back
+        rtc.set_stopped(false).unwrap();
+        assert_eq!(rtc.is_time_trusted(), Ok(false));
+        assert_eq!(rtc.get_time(), Ok(time));
+    }
+
+    #[test]
+    fn set_time_on_edge_times_out() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.store_record(1, &[0; RECORD_PAYLOAD_SIZE]).unwrap();
+
+        let mut pin = ScriptedPin::new(&[false]);
+        assert_eq!(
+            rtc.set_time_on_edge(
+                date_time(2024, 6, 1, 12, 0, 0),
+                &mut pin,
+                PulseEdge::Rising,
+                &mut NoDelay,
+                Duration::from_micros(100),
+            ),
+            Err(Error::Timeout)
+        );
+        // One read for the low level, then one per poll and a last one
+        assert_eq!(pin.reads, 102);
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Stopped));
+        assert_eq!(rtc.is_time_trusted(), Ok(false));
+    }
+}
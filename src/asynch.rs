@@ -0,0 +1,616 @@
+//! Async twin of `Rx8010sj`, on top of `embedded_hal_async`.
+//! Register encoding and validation are shared with the blocking driver,
+//! only the bus accesses differ.
+
+use core::time::Duration;
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+use crate::ram::ram_register;
+use crate::registers::{
+    BIT_REGISTER_CONTROL_AIE, BIT_REGISTER_CONTROL_STOP, BIT_REGISTER_CONTROL_TIE,
+    BIT_REGISTER_EXTENSION_WADA, BIT_REGISTER_FLAG_AF, BIT_REGISTER_FLAG_TF, BIT_REGISTER_FLAG_VLF,
+    INIT_REGISTERS, MASK_REGISTER_EXTENSION_TSEL, MASK_REGISTER_FLAG, REGISTER_ALARM_MIN,
+    REGISTER_CONTROL, REGISTER_EXTENSION, REGISTER_FLAG, REGISTER_SEC, REGISTER_TIMER_COUNTER_0,
+};
+use crate::timer::{RESTART_TIMER, START_TIMER, STOP_TIMER};
+use crate::{
+    burst_buffer, clear_test, encode_time, released_control, replace_bits, time_from_registers,
+    Alarm, DateTime, Error, InitStatus, InterruptFlags, PulseEdge, Record, TimeValidity,
+    TimerConfig, COLD_START_REGISTERS, DEFAULT_ADDRESS, POWER_ON_DELAY_MS, RAM_SIZE,
+    TIME_READ_SIZE,
+};
+
+/// RX-8010-SJ async driver (utilizes the embedded_hal_async i2c interface)
+pub struct Rx8010sjAsync<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C, E> Rx8010sjAsync<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// New driver instance, assumes that there is no i2c mux
+    /// sitting between the RTC and the host.
+    pub fn new(i2c: I2C) -> Self {
+        Rx8010sjAsync {
+            i2c,
+            address: DEFAULT_ADDRESS,
+        }
+    }
+
+    pub fn with_address(self, address: u8) -> Self {
+        Rx8010sjAsync { address, ..self }
+    }
+
+    /// See `Rx8010sj::init`
+    pub async fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<InitStatus, Error<E>> {
+        delay.delay_ms(POWER_ON_DELAY_MS).await;
+
+        let flag_register = self.read_register(REGISTER_FLAG).await?;
+        let cold_start = (flag_register & BIT_REGISTER_FLAG_VLF) > 0;
+
+        for (reg, value) in INIT_REGISTERS {
+            self.write_register(reg, value).await?;
+        }
+
+        if cold_start {
+            self.write_registers(REGISTER_EXTENSION, &COLD_START_REGISTERS)
+                .await?;
+            self.set_time_trusted(false).await?;
+            Ok(InitStatus::ColdStart)
+        } else {
+            let control_register = self.read_register(REGISTER_CONTROL).await?;
+            if let Some(control_register) = clear_test(control_register) {
+                self.write_register(REGISTER_CONTROL, control_register)
+                    .await?;
+            }
+            Ok(InitStatus::TimeRetained)
+        }
+    }
+
+    pub async fn is_stopped(&mut self) -> Result<bool, Error<E>> {
+        let control_register = self.read_register(REGISTER_CONTROL).await?;
+        Ok((control_register & BIT_REGISTER_CONTROL_STOP) > 0)
+    }
+
+    /// See `Rx8010sj::set_stopped`
+    pub async fn set_stopped(&mut self, stopped: bool) -> Result<(), Error<E>> {
+        if stopped {
+            self.set_time_trusted(false).await?;
+        }
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_STOP,
+            if stopped {
+                BIT_REGISTER_CONTROL_STOP
+            } else {
+                0
+            },
+        )
+        .await
+    }
+
+    pub async fn is_voltage_low(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG).await?;
+        Ok((flag_register & BIT_REGISTER_FLAG_VLF) > 0)
+    }
+
+    /// See `Rx8010sj::clear_voltage_low`
+    pub async fn clear_voltage_low(&mut self) -> Result<(), Error<E>> {
+        self.set_time_trusted(false).await?;
+        self.clear_flags(BIT_REGISTER_FLAG_VLF).await
+    }
+
+    pub async fn time_validity(&mut self) -> Result<TimeValidity, Error<E>> {
+        let [flag_register, control_register] = self.read_registers::<2>(REGISTER_FLAG).await?;
+        Ok(TimeValidity::from_registers(
+            flag_register,
+            control_register,
+        ))
+    }
+
+    /// See `Rx8010sj::get_time`
+    pub async fn get_time(&mut self) -> Result<DateTime, Error<E>> {
+        let registers = self.read_registers::<TIME_READ_SIZE>(REGISTER_SEC).await?;
+        time_from_registers(&registers)
+    }
+
+    /// See `Rx8010sj::set_time`
+    pub async fn set_time(&mut self, date_time: DateTime) -> Result<(), Error<E>> {
+        let control_register = self.stop_and_write_time(&date_time).await?;
+        self.write_register(REGISTER_CONTROL, control_register)
+            .await?;
+        self.time_set().await
+    }
+
+    /// See `Rx8010sj::set_time_on_edge`; the edge is awaited through `Wait`
+    /// instead of polled, so its latency depends on the executor.
+    /// There is no built-in timeout: race the future against a timer
+    /// instead. A pin error, or dropping the future before the edge, leaves
+    /// the chip in the same state as a failed blocking wait (clock stopped,
+    /// time not trusted).
+    pub async fn set_time_on_edge<P: Wait>(
+        &mut self,
+        date_time: DateTime,
+        pin: &mut P,
+        edge: PulseEdge,
+    ) -> Result<(), Error<E>> {
+        let control_register = self.stop_and_write_time(&date_time).await?;
+        self.set_time_trusted(false).await?;
+        match edge {
+            PulseEdge::Rising => pin.wait_for_rising_edge().await,
+            PulseEdge::Falling => pin.wait_for_falling_edge().await,
+        }
+        .map_err(|_| Error::Pin)?;
+        self.write_register(REGISTER_CONTROL, control_register)
+            .await?;
+        self.time_set().await
+    }
+
+    /// See `Rx8010sj::stop_and_write_time`
+    async fn stop_and_write_time(&mut self, date_time: &DateTime) -> Result<u8, Error<E>> {
+        let time_registers = encode_time(date_time).ok_or(Error::UnsupportedDate)?;
+        let control_register = self.read_register(REGISTER_CONTROL).await?;
+        self.write_register(
+            REGISTER_CONTROL,
+            control_register | BIT_REGISTER_CONTROL_STOP,
+        )
+        .await?;
+        self.write_registers(REGISTER_SEC, &time_registers).await?;
+        Ok(released_control(control_register))
+    }
+
+    /// See `Rx8010sj::time_set`
+    async fn time_set(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_VLF).await?;
+        self.set_time_trusted(true).await
+    }
+
+    /// See `Rx8010sj::set_time_trusted`
+    async fn set_time_trusted(&mut self, trusted: bool) -> Result<(), Error<E>> {
+        let mut bytes = [0; RAM_SIZE];
+        self.read_ram(0, &mut bytes).await?;
+        if let Some(bytes) = Record::with_time_trusted(&bytes, trusted) {
+            self.write_ram(0, &bytes).await?;
+        }
+        Ok(())
+    }
+
+    /// See `Rx8010sj::set_alarm`
+    pub async fn set_alarm(&mut self, alarm: &Alarm) -> Result<(), Error<E>> {
+        let (registers, wada) = alarm.to_registers().ok_or(Error::InvalidArgument)?;
+        self.write_registers(REGISTER_ALARM_MIN, &registers).await?;
+        self.modify_register(REGISTER_EXTENSION, BIT_REGISTER_EXTENSION_WADA, wada)
+            .await?;
+        self.clear_flags(BIT_REGISTER_FLAG_AF).await
+    }
+
+    pub async fn get_alarm(&mut self) -> Result<Alarm, Error<E>> {
+        let registers = self.read_registers::<3>(REGISTER_ALARM_MIN).await?;
+        let extension_register = self.read_register(REGISTER_EXTENSION).await?;
+        Alarm::from_registers(registers, extension_register).ok_or(Error::InvalidRegister)
+    }
+
+    pub async fn enable_alarm(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_AIE,
+            BIT_REGISTER_CONTROL_AIE,
+        )
+        .await
+    }
+
+    pub async fn disable_alarm(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(REGISTER_CONTROL, BIT_REGISTER_CONTROL_AIE, 0)
+            .await
+    }
+
+    pub async fn is_alarm_triggered(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG).await?;
+        Ok((flag_register & BIT_REGISTER_FLAG_AF) > 0)
+    }
+
+    pub async fn acknowledge_alarm(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_AF).await
+    }
+
+    /// See `Rx8010sj::set_timer`
+    pub async fn set_timer(&mut self, config: TimerConfig) -> Result<(), Error<E>> {
+        let (counter, tsel) = config.to_registers().ok_or(Error::InvalidArgument)?;
+        self.modify_registers(&STOP_TIMER).await?;
+        self.write_registers(REGISTER_TIMER_COUNTER_0, &counter)
+            .await?;
+        self.modify_register(REGISTER_EXTENSION, MASK_REGISTER_EXTENSION_TSEL, tsel)
+            .await
+    }
+
+    pub async fn set_timer_duration(&mut self, duration: Duration) -> Result<(), Error<E>> {
+        let config = TimerConfig::from_duration(duration).ok_or(Error::InvalidArgument)?;
+        self.set_timer(config).await
+    }
+
+    pub async fn get_timer(&mut self) -> Result<TimerConfig, Error<E>> {
+        let counter = self.read_registers::<2>(REGISTER_TIMER_COUNTER_0).await?;
+        let extension_register = self.read_register(REGISTER_EXTENSION).await?;
+        Ok(TimerConfig::from_registers(counter, extension_register))
+    }
+
+    pub async fn start_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&START_TIMER).await
+    }
+
+    pub async fn stop_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&STOP_TIMER).await
+    }
+
+    pub async fn restart_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&RESTART_TIMER).await
+    }
+
+    pub async fn enable_timer_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_TIE,
+            BIT_REGISTER_CONTROL_TIE,
+        )
+        .await
+    }
+
+    pub async fn disable_timer_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(REGISTER_CONTROL, BIT_REGISTER_CONTROL_TIE, 0)
+            .await
+    }
+
+    pub async fn is_timer_expired(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG).await?;
+        Ok((flag_register & BIT_REGISTER_FLAG_TF) > 0)
+    }
+
+    pub async fn clear_timer_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_TF).await
+    }
+
+    pub async fn read_ram(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), Error<E>> {
+        match ram_register(offset, buf.len())? {
+            Some(reg) => self.read_registers_into(reg, buf).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn write_ram(&mut self, offset: usize, data: &[u8]) -> Result<(), Error<E>> {
+        match ram_register(offset, data.len())? {
+            Some(reg) => self.write_registers(reg, data).await,
+            None => Ok(()),
+        }
+    }
+
+    /// See `Rx8010sj::handle_interrupt`
+    pub async fn handle_interrupt(&mut self) -> Result<InterruptFlags, Error<E>> {
+        let flags = InterruptFlags::from_register(self.read_register(REGISTER_FLAG).await?);
+        if let Some(to_clear) = flags.to_clear() {
+            self.clear_flags(to_clear).await?;
+        }
+        Ok(flags)
+    }
+
+    /// Waits for /IRQ (wired to `irq`) to go low, then decodes and clears the
+    /// flags and returns the alarm, timer and update events that fired.
+    /// Returns right away if an event is already pending; if the line is
+    /// pulled low by another device sharing it, waits for it to be released
+    /// and keeps listening.
+    pub async fn next_event<P: Wait>(&mut self, irq: &mut P) -> Result<InterruptFlags, Error<E>> {
+        let events = InterruptFlags::ALARM | InterruptFlags::TIMER | InterruptFlags::UPDATE;
+
+        loop {
+            irq.wait_for_low().await.map_err(|_| Error::Pin)?;
+
+            let flags = self.handle_interrupt().await?;
+            if flags.intersects(events) {
+                return Ok(flags);
+            }
+
+            irq.wait_for_high().await.map_err(|_| Error::Pin)?;
+        }
+    }
+
+    async fn clear_flags(&mut self, flags: u8) -> Result<(), Error<E>> {
+        self.write_register(REGISTER_FLAG, MASK_REGISTER_FLAG & (!flags))
+            .await
+    }
+
+    async fn modify_register(&mut self, reg: u8, mask: u8, value: u8) -> Result<(), Error<E>> {
+        let register = self.read_register(reg).await?;
+        self.write_register(reg, replace_bits(register, mask, value))
+            .await
+    }
+
+    async fn modify_registers(&mut self, steps: &[(u8, u8, u8)]) -> Result<(), Error<E>> {
+        for &(reg, mask, value) in steps {
+            self.modify_register(reg, mask, value).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_register(&mut self, reg: u8, data: u8) -> Result<(), Error<E>> {
+        self.i2c
+            .write(self.address, &[reg, data])
+            .await
+            .map_err(Error::I2c)
+    }
+
+    async fn write_registers(&mut self, reg: u8, data: &[u8]) -> Result<(), Error<E>> {
+        let buffer = burst_buffer(reg, data)?;
+        self.i2c
+            .write(self.address, &buffer[..data.len() + 1])
+            .await
+            .map_err(Error::I2c)
+    }
+
+    async fn read_register(&mut self, reg: u8) -> Result<u8, Error<E>> {
+        self.read_registers::<1>(reg).await.map(|regs| regs[0])
+    }
+
+    async fn read_registers<const N: usize>(&mut self, reg: u8) -> Result<[u8; N], Error<E>> {
+        let mut buf: [u8; N] = [0; N];
+        self.read_registers_into(reg, &mut buf).await?;
+        Ok(buf)
+    }
+
+    async fn read_registers_into(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.i2c
+            .write_read(self.address, &[reg], buf)
+            .await
+            .map_err(Error::I2c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_async::digital::{ErrorKind, ErrorType};
+
+    use super::*;
+    use crate::registers::Register;
+    use crate::sim::testing::{block_on, date_time, poll_once, running_at, NoDelay};
+    use crate::sim::Rx8010sjSim;
+    use crate::{AlarmDay, RecordStatus, Rx8010sj, TimerClock, RECORD_PAYLOAD_SIZE};
+
+    /// Synchronization pulse whose edges come (or fail) right away
+    struct PulsePin(Result<(), ErrorKind>);
+
+    impl ErrorType for PulsePin {
+        type Error = ErrorKind;
+    }
+
+    impl Wait for PulsePin {
+        async fn wait_for_high(&mut self) -> Result<(), ErrorKind> {
+            self.0
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), ErrorKind> {
+            self.0
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), ErrorKind> {
+            self.0
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), ErrorKind> {
+            self.0
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), ErrorKind> {
+            self.0
+        }
+    }
+
+    /// /IRQ line held at one level: waiting for the other one, or for an
+    /// edge, never completes
+    struct IrqLine {
+        low: bool,
+    }
+
+    impl ErrorType for IrqLine {
+        type Error = ErrorKind;
+    }
+
+    impl Wait for IrqLine {
+        async fn wait_for_high(&mut self) -> Result<(), ErrorKind> {
+            if self.low {
+                core::future::pending().await
+            }
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), ErrorKind> {
+            if !self.low {
+                core::future::pending().await
+            }
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), ErrorKind> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), ErrorKind> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), ErrorKind> {
+            core::future::pending().await
+        }
+    }
+
+    /// Simulator running at 2024-01-01T00:00:00, with an untrusted record
+    fn sim_with_record() -> Rx8010sjSim {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        Rx8010sj::new(&mut sim)
+            .store_record(1, &[0; RECORD_PAYLOAD_SIZE])
+            .unwrap();
+        sim
+    }
+
+    fn time_trusted(sim: &mut Rx8010sjSim) -> bool {
+        match Rx8010sj::new(sim).load_record() {
+            Ok(RecordStatus::Valid(record)) => record.time_trusted,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn init_and_set_time() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        assert_eq!(block_on(rtc.init(&mut NoDelay)), Ok(InitStatus::ColdStart));
+        assert_eq!(block_on(rtc.get_time()), Err(Error::VoltageLow));
+
+        let time = date_time(2024, 2, 29, 23, 59, 59);
+        block_on(rtc.set_time(time)).unwrap();
+        assert_eq!(block_on(rtc.time_validity()), Ok(TimeValidity::Valid));
+        assert_eq!(block_on(rtc.get_time()), Ok(time));
+        assert_eq!(
+            block_on(rtc.init(&mut NoDelay)),
+            Ok(InitStatus::TimeRetained)
+        );
+
+        sim.advance(Duration::from_secs(1));
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        assert_eq!(block_on(rtc.get_time()), Ok(date_time(2024, 3, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn set_time_marks_the_record() {
+        let mut sim = sim_with_record();
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        block_on(rtc.set_stopped(true)).unwrap();
+        assert!(!time_trusted(&mut sim));
+
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        block_on(rtc.set_time(date_time(2024, 1, 1, 0, 0, 0))).unwrap();
+        assert!(time_trusted(&mut sim));
+    }
+
+    #[test]
+    fn set_time_on_edge() {
+        let mut sim = sim_with_record();
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let time = date_time(2024, 6, 1, 12, 0, 0);
+        block_on(rtc.set_time_on_edge(time, &mut PulsePin(Ok(())), PulseEdge::Rising)).unwrap();
+        assert_eq!(block_on(rtc.time_validity()), Ok(TimeValidity::Valid));
+        assert_eq!(block_on(rtc.get_time()), Ok(time));
+        assert!(time_trusted(&mut sim));
+    }
+
+    #[test]
+    fn set_time_on_edge_pin_error_leaves_the_clock_stopped() {
+        let mut sim = sim_with_record();
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let time = date_time(2024, 6, 1, 12, 0, 0);
+        let mut pin = PulsePin(Err(ErrorKind::Other));
+        assert_eq!(
+            block_on(rtc.set_time_on_edge(time, &mut pin, PulseEdge::Falling)),
+            Err(Error::Pin)
+        );
+        assert_eq!(block_on(rtc.time_validity()), Ok(TimeValidity::Stopped));
+        assert_eq!(sim.time(), Some(time));
+        assert!(!time_trusted(&mut sim));
+    }
+
+    #[test]
+    fn alarm_and_timer_events() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 59, 59));
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let alarm = Alarm {
+            minute: Some(0),
+            hour: Some(8),
+            day: AlarmDay::DayOfMonth(1),
+        };
+        block_on(rtc.set_alarm(&alarm)).unwrap();
+        assert_eq!(block_on(rtc.get_alarm()), Ok(alarm));
+        block_on(rtc.enable_alarm()).unwrap();
+
+        let config = TimerConfig {
+            clock: TimerClock::Hz64,
+            count: 32,
+        };
+        block_on(rtc.set_timer(config)).unwrap();
+        assert_eq!(block_on(rtc.get_timer()), Ok(config));
+        block_on(rtc.enable_timer_interrupt()).unwrap();
+        block_on(rtc.start_timer()).unwrap();
+
+        sim.advance(Duration::from_millis(501));
+        assert!(sim.irq_asserted());
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let flags = block_on(rtc.handle_interrupt()).unwrap();
+        assert!(flags.contains(InterruptFlags::TIMER));
+        assert!(!flags.contains(InterruptFlags::ALARM));
+
+        sim.advance(Duration::from_millis(500));
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let flags = block_on(rtc.handle_interrupt()).unwrap();
+        assert!(flags.contains(InterruptFlags::ALARM | InterruptFlags::TIMER));
+        assert_eq!(
+            block_on(rtc.handle_interrupt()),
+            Ok(InterruptFlags::default())
+        );
+        assert!(!sim.irq_asserted());
+    }
+
+    #[test]
+    fn ram_bounds() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        block_on(rtc.write_ram(13, &[1, 2, 3])).unwrap();
+        let mut buf = [0; 3];
+        block_on(rtc.read_ram(13, &mut buf)).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+        assert_eq!(
+            block_on(rtc.write_ram(14, &[0; 3])),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(block_on(rtc.read_ram(RAM_SIZE, &mut [])), Ok(()));
+    }
+
+    #[test]
+    fn next_event_returns_pending_events() {
+        let events = [
+            InterruptFlags::ALARM,
+            InterruptFlags::TIMER,
+            InterruptFlags::UPDATE,
+        ];
+        for event in events {
+            let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+            sim.set_register(Register::FLAG, event.bits());
+            let mut rtc = Rx8010sjAsync::new(&mut sim);
+            let mut irq = IrqLine { low: true };
+            assert_eq!(block_on(rtc.next_event(&mut irq)), Ok(event));
+            assert_eq!(sim.register(Register::FLAG), 0);
+        }
+    }
+
+    #[test]
+    fn next_event_keeps_waiting_without_events() {
+        for flags in [0, InterruptFlags::VOLTAGE_LOW.bits()] {
+            let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+            sim.set_register(Register::FLAG, flags);
+            let mut rtc = Rx8010sjAsync::new(&mut sim);
+            // Line held low by another device: released first, then awaited again
+            let mut irq = IrqLine { low: true };
+            assert!(poll_once(core::pin::pin!(rtc.next_event(&mut irq))).is_pending());
+            // VLF is never cleared by event handling
+            assert_eq!(sim.register(Register::FLAG), flags);
+        }
+
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        sim.set_register(Register::FLAG, InterruptFlags::ALARM.bits());
+        let mut rtc = Rx8010sjAsync::new(&mut sim);
+        let mut irq = IrqLine { low: false };
+        assert!(poll_once(core::pin::pin!(rtc.next_event(&mut irq))).is_pending());
+        // The flags are only read once the line goes low
+        assert_eq!(sim.register(Register::FLAG), InterruptFlags::ALARM.bits());
+    }
+}
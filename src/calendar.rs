@@ -0,0 +1,150 @@
+use core::fmt;
+
+/// Day of the week
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    /// 0 for Sunday up to 6 for Saturday, matching the bit position in the
+    /// WEEK register
+    pub fn number_from_sunday(self) -> u8 {
+        self as u8
+    }
+
+    /// `None` if `number` is not in 0-6
+    pub fn from_number_from_sunday(number: u8) -> Option<Weekday> {
+        Some(match number {
+            0 => Weekday::Sunday,
+            1 => Weekday::Monday,
+            2 => Weekday::Tuesday,
+            3 => Weekday::Wednesday,
+            4 => Weekday::Thursday,
+            5 => Weekday::Friday,
+            6 => Weekday::Saturday,
+            _ => return None,
+        })
+    }
+}
+
+/// A date from another calendar library cannot be represented as a `DateTime`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeRangeError;
+
+impl fmt::Display for DateTimeRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "date out of range")
+    }
+}
+
+impl core::error::Error for DateTimeRangeError {}
+
+/// Minimal, validated calendar date and time of day (proleptic Gregorian,
+/// no time zone). Orders chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl DateTime {
+    /// `None` if the fields do not make up a valid date and time.
+    /// Any year is accepted here; the RTC itself only handles 2000-2099.
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// 1-12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// 1-31
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // Sakamoto's method
+        const OFFSETS: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = if self.month < 3 {
+            self.year as u32 + 399
+        } else {
+            self.year as u32 + 400
+        };
+        let days = year + year / 4 - year / 100
+            + year / 400
+            + OFFSETS[(self.month - 1) as usize] as u32
+            + self.day as u32;
+        // `days % 7` is always in 0-6
+        Weekday::from_number_from_sunday((days % 7) as u8).unwrap_or(Weekday::Sunday)
+    }
+}
+
+/// ISO 8601, e.g. `2024-02-29T13:05:00`
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+pub(crate) fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
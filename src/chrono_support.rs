@@ -0,0 +1,111 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use embedded_hal::i2c::I2c;
+
+use crate::{DateTime, DateTimeRangeError, Error, Rx8010sj};
+
+impl From<DateTime> for NaiveDateTime {
+    fn from(date_time: DateTime) -> Self {
+        // Every `u16` year is within chrono's range (up to 262143)
+        let date = NaiveDate::from_ymd_opt(
+            date_time.year() as i32,
+            date_time.month() as u32,
+            date_time.day() as u32,
+        )
+        .unwrap();
+        let time = NaiveTime::from_hms_opt(
+            date_time.hour() as u32,
+            date_time.minute() as u32,
+            date_time.second() as u32,
+        )
+        .unwrap();
+        NaiveDateTime::new(date, time)
+    }
+}
+
+/// Fails for years that do not fit in a `u16` (including negative years);
+/// sub-second precision is dropped and a leap second reads as second 59.
+impl TryFrom<NaiveDateTime> for DateTime {
+    type Error = DateTimeRangeError;
+
+    fn try_from(date_time: NaiveDateTime) -> Result<Self, DateTimeRangeError> {
+        let year = u16::try_from(date_time.year()).map_err(|_| DateTimeRangeError)?;
+        DateTime::new(
+            year,
+            date_time.month() as u8,
+            date_time.day() as u8,
+            date_time.hour() as u8,
+            date_time.minute() as u8,
+            date_time.second() as u8,
+        )
+        .ok_or(DateTimeRangeError)
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// `get_time` as a `chrono::NaiveDateTime`
+    pub fn get_naive_datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        self.get_time().map(NaiveDateTime::from)
+    }
+
+    /// `set_time` from a `chrono::NaiveDateTime`; dates outside of 2000-2099
+    /// fail with `Error::UnsupportedDate`
+    pub fn set_naive_datetime(&mut self, date_time: &NaiveDateTime) -> Result<(), Error<E>> {
+        let date_time = DateTime::try_from(*date_time).map_err(|_| Error::UnsupportedDate)?;
+        self.set_time(date_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    fn naive(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn conversion_round_trip() {
+        let date_time = date_time(2024, 2, 29, 12, 34, 56);
+        let naive_date_time = NaiveDateTime::from(date_time);
+        assert_eq!(naive_date_time, naive(2024, 2, 29, 12, 34, 56));
+        assert_eq!(DateTime::try_from(naive_date_time), Ok(date_time));
+        assert_eq!(
+            DateTime::try_from(naive(-1, 1, 1, 0, 0, 0)),
+            Err(DateTimeRangeError)
+        );
+    }
+
+    #[test]
+    fn set_get_naive_datetime() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_naive_datetime(&naive(2099, 12, 31, 23, 59, 59))
+            .unwrap();
+        assert_eq!(
+            rtc.get_naive_datetime(),
+            Ok(naive(2099, 12, 31, 23, 59, 59))
+        );
+
+        for unsupported in [naive(1999, 12, 31, 23, 59, 59), naive(2100, 1, 1, 0, 0, 0)] {
+            assert_eq!(
+                rtc.set_naive_datetime(&unsupported),
+                Err(Error::UnsupportedDate)
+            );
+        }
+        assert_eq!(rtc.get_time(), Ok(date_time(2099, 12, 31, 23, 59, 59)));
+    }
+}
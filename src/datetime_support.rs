@@ -0,0 +1,58 @@
+use datetime::{DatePiece, LocalDate, LocalDateTime, LocalTime, Month, TimePiece};
+use embedded_hal::i2c::I2c;
+
+use crate::{DateTime, DateTimeRangeError, Error, Rx8010sj};
+
+impl From<DateTime> for LocalDateTime {
+    fn from(date_time: DateTime) -> Self {
+        // `datetime` takes any `i64` year, so every `u16` one fits
+        let date = LocalDate::ymd(
+            date_time.year() as i64,
+            Month::from_one(date_time.month() as i8).unwrap(),
+            date_time.day() as i8,
+        )
+        .unwrap();
+        let time = LocalTime::hms(
+            date_time.hour() as i8,
+            date_time.minute() as i8,
+            date_time.second() as i8,
+        )
+        .unwrap();
+        LocalDateTime::new(date, time)
+    }
+}
+
+/// Fails for years that do not fit in a `u16` (including negative years);
+/// milliseconds are dropped.
+impl TryFrom<LocalDateTime> for DateTime {
+    type Error = DateTimeRangeError;
+
+    fn try_from(date_time: LocalDateTime) -> Result<Self, DateTimeRangeError> {
+        let year = u16::try_from(date_time.year()).map_err(|_| DateTimeRangeError)?;
+        DateTime::new(
+            year,
+            date_time.month().months_from_january() as u8 + 1,
+            date_time.day() as u8,
+            date_time.hour() as u8,
+            date_time.minute() as u8,
+            date_time.second() as u8,
+        )
+        .ok_or(DateTimeRangeError)
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// `get_time` as a `datetime::LocalDateTime`
+    pub fn get_local_datetime(&mut self) -> Result<LocalDateTime, Error<E>> {
+        self.get_time().map(LocalDateTime::from)
+    }
+
+    /// `set_time` from a `datetime::LocalDateTime`, only 2000-2099 is supported
+    pub fn set_local_datetime(&mut self, date_time: LocalDateTime) -> Result<(), Error<E>> {
+        let date_time = DateTime::try_from(date_time).map_err(|_| Error::UnsupportedDate)?;
+        self.set_time(date_time)
+    }
+}
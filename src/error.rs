@@ -0,0 +1,44 @@
+use core::fmt;
+
+/// Errors reported by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    /// Underlying I2C bus error
+    I2c(E),
+    /// The registers hold values that cannot be decoded (e.g. bad BCD digits)
+    InvalidRegister,
+    /// A parameter is out of the range accepted by the chip
+    InvalidArgument,
+    /// Access outside of the user RAM or past the end of the register map,
+    /// or a burst longer than the register map
+    OutOfBounds,
+    /// A GPIO pin (/IRQ, synchronization pulse) reported an error
+    Pin,
+    /// The date is outside of the range supported by the RTC calendar (2000-2099)
+    UnsupportedDate,
+    /// A wait (e.g. for a synchronization pulse edge) did not complete in time
+    Timeout,
+    /// The STOP bit is set, the clock is not counting
+    ClockStopped,
+    /// The VLF flag is set: the oscillator stopped because of a low supply
+    /// voltage and the time can no longer be trusted
+    VoltageLow,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::I2c(e) => write!(f, "I2C bus error: {:?}", e),
+            Error::InvalidRegister => write!(f, "invalid register contents"),
+            Error::InvalidArgument => write!(f, "invalid argument"),
+            Error::OutOfBounds => write!(f, "access out of bounds"),
+            Error::Pin => write!(f, "pin error"),
+            Error::UnsupportedDate => write!(f, "date outside of 2000-2099"),
+            Error::Timeout => write!(f, "timed out"),
+            Error::ClockStopped => write!(f, "clock is stopped"),
+            Error::VoltageLow => write!(f, "voltage low detected, time is invalid"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for Error<E> {}
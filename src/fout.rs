@@ -0,0 +1,57 @@
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{MASK_REGISTER_EXTENSION_FSEL, REGISTER_EXTENSION};
+use crate::{Error, Rx8010sj};
+
+/// Frequency of the FOUT clock output (FSEL bits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoutFrequency {
+    /// Output disabled, lowest current consumption
+    Off,
+    Hz32768,
+    Hz1024,
+    Hz1,
+}
+
+impl FoutFrequency {
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            FoutFrequency::Off => 0x00,
+            FoutFrequency::Hz32768 => 0x40,
+            FoutFrequency::Hz1024 => 0x80,
+            FoutFrequency::Hz1 => 0xC0,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & MASK_REGISTER_EXTENSION_FSEL {
+            0x00 => FoutFrequency::Off,
+            0x40 => FoutFrequency::Hz32768,
+            0x80 => FoutFrequency::Hz1024,
+            _ => FoutFrequency::Hz1,
+        }
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    pub fn set_fout_frequency(&mut self, frequency: FoutFrequency) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_EXTENSION,
+            MASK_REGISTER_EXTENSION_FSEL,
+            frequency.bits(),
+        )
+    }
+
+    pub fn get_fout_frequency(&mut self) -> Result<FoutFrequency, Error<E>> {
+        let extension_register = self.read_register(REGISTER_EXTENSION)?;
+        Ok(FoutFrequency::from_bits(extension_register))
+    }
+
+    /// Turns the FOUT output off to save current
+    pub fn disable_fout(&mut self) -> Result<(), Error<E>> {
+        self.set_fout_frequency(FoutFrequency::Off)
+    }
+}
@@ -0,0 +1,116 @@
+use core::ops::BitOr;
+
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{
+    BIT_REGISTER_FLAG_AF, BIT_REGISTER_FLAG_TF, BIT_REGISTER_FLAG_UF, BIT_REGISTER_FLAG_VLF,
+    MASK_REGISTER_FLAG, REGISTER_FLAG,
+};
+use crate::{Error, Rx8010sj};
+
+/// Set of events reported by the Flag register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InterruptFlags(u8);
+
+impl InterruptFlags {
+    pub const ALARM: InterruptFlags = InterruptFlags(BIT_REGISTER_FLAG_AF);
+    pub const TIMER: InterruptFlags = InterruptFlags(BIT_REGISTER_FLAG_TF);
+    pub const UPDATE: InterruptFlags = InterruptFlags(BIT_REGISTER_FLAG_UF);
+    pub const VOLTAGE_LOW: InterruptFlags = InterruptFlags(BIT_REGISTER_FLAG_VLF);
+
+    /// Keeps only the event bits of a raw Flag register value
+    pub fn from_register(flag_register: u8) -> Self {
+        InterruptFlags(flag_register & MASK_REGISTER_FLAG)
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, other: InterruptFlags) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    pub fn intersects(self, other: InterruptFlags) -> bool {
+        (self.0 & other.0) != 0
+    }
+
+    /// Flags to clear once reported: the events, never VLF.
+    /// `None` if there is nothing to clear.
+    pub(crate) fn to_clear(self) -> Option<u8> {
+        Some(self.0 & (!BIT_REGISTER_FLAG_VLF)).filter(|flags| *flags != 0)
+    }
+}
+
+impl BitOr for InterruptFlags {
+    type Output = InterruptFlags;
+
+    fn bitor(self, rhs: InterruptFlags) -> InterruptFlags {
+        InterruptFlags(self.0 | rhs.0)
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Reads the Flag register once and clears the alarm, timer and update
+    /// flags that were found set. Flags raised after the read are written
+    /// back as 1 (no effect), so they stay pending for the next call.
+    /// VLF is reported but left set: only setting the time clears it.
+    pub fn handle_interrupt(&mut self) -> Result<InterruptFlags, Error<E>> {
+        let flags = InterruptFlags::from_register(self.read_register(REGISTER_FLAG)?);
+        if let Some(to_clear) = flags.to_clear() {
+            self.clear_flags(to_clear)?;
+        }
+        Ok(flags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+    use crate::{Alarm, AlarmDay, TimerClock, TimerConfig};
+
+    #[test]
+    fn handle_interrupt_clears_events() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 59, 59));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_alarm(&Alarm {
+            minute: None,
+            hour: None,
+            day: AlarmDay::Any,
+        })
+        .unwrap();
+        rtc.enable_alarm().unwrap();
+        rtc.set_timer(TimerConfig {
+            clock: TimerClock::Hz1,
+            count: 1,
+        })
+        .unwrap();
+        rtc.start_timer().unwrap();
+        sim.set_voltage_low();
+
+        sim.advance(Duration::from_secs(1));
+        assert!(sim.irq_asserted());
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let flags = rtc.handle_interrupt().unwrap();
+        assert!(flags.contains(
+            InterruptFlags::ALARM
+                | InterruptFlags::TIMER
+                | InterruptFlags::UPDATE
+                | InterruptFlags::VOLTAGE_LOW
+        ));
+
+        // VLF is reported again, the events are gone
+        assert_eq!(rtc.handle_interrupt(), Ok(InterruptFlags::VOLTAGE_LOW));
+        assert!(!sim.irq_asserted());
+    }
+}
@@ -1,15 +1,148 @@
-use datetime::*;
+#![no_std]
+
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::I2c;
 
+mod alarm;
+mod aligned;
+#[cfg(feature = "async")]
+mod asynch;
+mod calendar;
+#[cfg(feature = "chrono")]
+mod chrono_support;
+#[cfg(feature = "datetime")]
+mod datetime_support;
+mod error;
+mod fout;
+mod interrupt;
+mod ram;
+mod record;
+pub mod registers;
+#[cfg(feature = "rtcc")]
+mod rtcc_support;
+#[cfg(any(test, feature = "sim"))]
+#[cfg_attr(not(feature = "sim"), allow(dead_code))]
+mod sim;
+mod snapshot;
+#[cfg(feature = "embedded-storage")]
+mod storage;
+#[cfg(feature = "time")]
+mod time_support;
+mod timer;
+mod unix;
+mod update;
+
+pub use alarm::{Alarm, AlarmDay, Weekdays};
+pub use aligned::PulseEdge;
+#[cfg(feature = "async")]
+pub use asynch::Rx8010sjAsync;
+pub use calendar::{DateTime, DateTimeRangeError, Weekday};
+pub use error::Error;
+pub use fout::FoutFrequency;
+pub use interrupt::InterruptFlags;
+pub use ram::RAM_SIZE;
+pub use record::{Record, RecordStatus, RECORD_PAYLOAD_SIZE};
+#[cfg(feature = "sim")]
+pub use sim::Rx8010sjSim;
+pub use snapshot::RegisterSnapshot;
+#[cfg(feature = "embedded-storage")]
+pub use storage::RamStorage;
+pub use timer::{TimerClock, TimerConfig};
+pub use update::UpdateInterval;
+
+use registers::{
+    BIT_REGISTER_CONTROL_STOP, BIT_REGISTER_CONTROL_TEST, BIT_REGISTER_FLAG_VLF, INIT_REGISTERS,
+    MASK_REGISTER_CONTROL_CSEL, MASK_REGISTER_FLAG, REGISTER_CONTROL, REGISTER_EXTENSION,
+    REGISTER_FLAG, REGISTER_IRQ_CONTROL, REGISTER_SEC,
+};
+
 const DEFAULT_ADDRESS: u8 = 0x64 >> 1;
-const REGISTER_CONTROL: u8 = 0x1F;
-const REGISTER_SEC: u8 = 0x10;
+/// Time the oscillator needs after power-on before the chip can be accessed
+const POWER_ON_DELAY_MS: u32 = 40;
+
+/// Longest burst: the whole register map
+const MAX_BURST_LEN: usize = (REGISTER_IRQ_CONTROL - REGISTER_SEC) as usize + 1;
+
+/// Extension, Flag and Control after a cold start: interrupts and outputs
+/// off, VLF left set
+const COLD_START_REGISTERS: [u8; 3] = [0x00, BIT_REGISTER_FLAG_VLF, 0x00];
+
+/// SEC..CONTROL, read at once by `get_time`
+const TIME_READ_SIZE: usize = (REGISTER_CONTROL - REGISTER_SEC) as usize + 1;
+
+/// The YEAR register only holds two digits, the chip counts 2000-2099
+const CENTURY: u16 = 2000;
+
+/// Outcome of the power-on initialization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStatus {
+    /// VLF was set: the chip went through a cold power-on (or lost its backup
+    /// supply), the registers were reset to defaults and the time must be set again
+    ColdStart,
+    /// The chip kept running, time and configuration were retained
+    TimeRetained,
+}
+
+/// Whether the time kept by the chip can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeValidity {
+    /// The clock is running and never lost power
+    Valid,
+    /// The STOP bit is set
+    Stopped,
+    /// The backup supply dropped too low (VLF): the time must be set again
+    VoltageLow,
+}
+
+/// How often the temperature compensation runs (CSEL bits): a shorter
+/// interval is more accurate, a longer one draws less current
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompensationInterval {
+    Ms500,
+    S2,
+    S10,
+    S30,
+}
+
+impl TimeValidity {
+    pub(crate) fn from_registers(flag_register: u8, control_register: u8) -> Self {
+        if (flag_register & BIT_REGISTER_FLAG_VLF) > 0 {
+            TimeValidity::VoltageLow
+        } else if (control_register & BIT_REGISTER_CONTROL_STOP) > 0 {
+            TimeValidity::Stopped
+        } else {
+            TimeValidity::Valid
+        }
+    }
+}
 
-const BIT_REGISTER_CONTROL_STOP: u8 = 0x40;
+impl CompensationInterval {
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            CompensationInterval::Ms500 => 0b00,
+            CompensationInterval::S2 => 0b01,
+            CompensationInterval::S10 => 0b10,
+            CompensationInterval::S30 => 0b11,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & MASK_REGISTER_CONTROL_CSEL {
+            0b00 => CompensationInterval::Ms500,
+            0b01 => CompensationInterval::S2,
+            0b10 => CompensationInterval::S10,
+            _ => CompensationInterval::S30,
+        }
+    }
+}
 
 /// RX-8010-SJ
 /// Real-Time Clock (RTC) Module with I2C-Bus Interface
 /// rust no_std driver (utilizes the embedded_hal i2c interface)
+///
+/// Times are exchanged as the built-in `DateTime`; the `datetime`, `chrono`
+/// and `time` features add conversions to and from `datetime::LocalDateTime`,
+/// `chrono::NaiveDateTime` and `time::PrimitiveDateTime`/`OffsetDateTime`.
 pub struct Rx8010sj<I2C> {
     i2c: I2C,
     address: u8,
@@ -28,118 +161,491 @@ where
         }
     }
 
-    pub fn with_address(self: Self, address: u8) -> Self {
+    pub fn with_address(self, address: u8) -> Self {
         Rx8010sj { address, ..self }
     }
 
-    pub fn is_stopped(self: &mut Self) -> Result<bool, E> {
+    /// Datasheet initialization sequence, to be run once at start-up.
+    /// Waits for the oscillator to settle, writes the reserved registers,
+    /// resets IRQ control and clears the TEST bit; after a cold power-on
+    /// Extension, Flag and Control are also reset to defaults (all
+    /// interrupts and outputs disabled).
+    /// VLF is left set on cold start so that `get_time` keeps failing until
+    /// the time is set.
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<InitStatus, Error<E>> {
+        delay.delay_ms(POWER_ON_DELAY_MS);
+
+        let flag_register = self.read_register(REGISTER_FLAG)?;
+        let cold_start = (flag_register & BIT_REGISTER_FLAG_VLF) > 0;
+
+        for (reg, value) in INIT_REGISTERS {
+            self.write_register(reg, value)?;
+        }
+
+        if cold_start {
+            self.write_registers(REGISTER_EXTENSION, &COLD_START_REGISTERS)?;
+            self.set_time_trusted(false)?;
+            Ok(InitStatus::ColdStart)
+        } else {
+            let control_register = self.read_register(REGISTER_CONTROL)?;
+            if let Some(control_register) = clear_test(control_register) {
+                self.write_register(REGISTER_CONTROL, control_register)?;
+            }
+            Ok(InitStatus::TimeRetained)
+        }
+    }
+
+    pub fn is_stopped(&mut self) -> Result<bool, Error<E>> {
         let control_register = self.read_register(REGISTER_CONTROL)?;
         Ok((control_register & BIT_REGISTER_CONTROL_STOP) > 0)
     }
 
-    pub fn set_stopped(self: &mut Self, stopped: bool) -> Result<(), E> {
-        let control_register = self.read_register(REGISTER_CONTROL)?;
-        self.write_register(
+    /// Stopping the clock also clears the trusted time marker of the stored
+    /// record (if any): restarting it does not make the time valid again.
+    pub fn set_stopped(&mut self, stopped: bool) -> Result<(), Error<E>> {
+        if stopped {
+            self.set_time_trusted(false)?;
+        }
+        self.modify_register(
             REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_STOP,
             if stopped {
-                control_register | BIT_REGISTER_CONTROL_STOP
+                BIT_REGISTER_CONTROL_STOP
             } else {
-                control_register & (!BIT_REGISTER_CONTROL_STOP)
+                0
             },
-        )?;
-        Ok(())
+        )
     }
 
-    pub fn get_time(self: &mut Self) -> Result<LocalDateTime, E> {
-        let time_registers = self.read_registers::<7>(REGISTER_SEC)?;
+    pub fn set_compensation_interval(
+        &mut self,
+        interval: CompensationInterval,
+    ) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            MASK_REGISTER_CONTROL_CSEL,
+            interval.bits(),
+        )
+    }
 
-        let sec = bcd2bin(time_registers[0]);
-        let min = bcd2bin(time_registers[1]);
-        let hour = bcd2bin(time_registers[2]);
-        let wday = bcd2bin(time_registers[3]);
-        let day = bcd2bin(time_registers[4]);
-        let month = bcd2bin(time_registers[5]);
-        let year = bcd2bin(time_registers[6]);
+    pub fn get_compensation_interval(&mut self) -> Result<CompensationInterval, Error<E>> {
+        let control_register = self.read_register(REGISTER_CONTROL)?;
+        Ok(CompensationInterval::from_bits(control_register))
+    }
 
-        let date = LocalDate::ymd(
-            year as i64,
-            Month::from_zero(month as i8).unwrap_or(Month::January),
-            day as i8,
-        )
-        .unwrap_or(LocalDate::yd(1970, 0).unwrap());
-
-        let time = LocalTime::hms(hour as i8, min as i8, sec as i8)
-            .unwrap_or(LocalTime::hm(0, 0).unwrap());
-
-        Ok(LocalDateTime::new(date, time))
-    }
-
-    pub fn set_time(self: &mut Self, date_time: LocalDateTime) -> Result<(), E> {
-        let date = date_time.date();
-        let time = date_time.time();
-
-        let time_registers: [u8;7] =[ bin2bcd(time.second() as u8),
-         bin2bcd(time.minute() as u8)
-         ,bin2bcd(time.hour() as u8)
-         ,bin2bcd(match date.weekday() {Weekday::Sunday => 0, Weekday::Monday => 1, Weekday::Tuesday => 2, Weekday::Wednesday => 3, Weekday::Thursday => 4, Weekday::Friday => 5, Weekday::Saturday=>6} )
-         ,bin2bcd(date.day() as u8)
-         ,bin2bcd(match date.month() {
-            Month::January => 0,
-            Month::February => 1,
-            Month::March => 2,
-            Month::April => 3,
-            Month::May => 4,
-            Month::June => 5,
-            Month::July => 6,
-            Month::August => 7,
-            Month::September => 8,
-            Month::October => 9,
-            Month::November => 10,
-            Month::December => 11,
-        })
-         ,bin2bcd((date.year() - 1900) as u8)];
-
-        self.write_registers(REGISTER_SEC,&time_registers)?;
+    /// Reads the VLF flag, set when the supply dropped low enough to stop the
+    /// oscillator (including the first power-on).
+    pub fn is_voltage_low(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG)?;
+        Ok((flag_register & BIT_REGISTER_FLAG_VLF) > 0)
+    }
 
-        Ok(())
+    /// Clears the VLF flag; `set_time` already takes care of it.
+    /// The time itself is still unreliable, so the trusted time marker of
+    /// the stored record (if any) is cleared first.
+    pub fn clear_voltage_low(&mut self) -> Result<(), Error<E>> {
+        self.set_time_trusted(false)?;
+        self.clear_flags(BIT_REGISTER_FLAG_VLF)
+    }
+
+    /// Checks VLF and STOP with a single read.
+    pub fn time_validity(&mut self) -> Result<TimeValidity, Error<E>> {
+        let [flag_register, control_register] = self.read_registers::<2>(REGISTER_FLAG)?;
+        Ok(TimeValidity::from_registers(
+            flag_register,
+            control_register,
+        ))
     }
 
+    /// Reads the calendar.
+    /// Fails with `VoltageLow` or `ClockStopped` if the time cannot be trusted
+    /// and with `InvalidRegister` if the registers do not hold a valid date.
+    pub fn get_time(&mut self) -> Result<DateTime, Error<E>> {
+        // SEC..CONTROL in a single burst, so the flags match the time read
+        let registers = self.read_registers::<TIME_READ_SIZE>(REGISTER_SEC)?;
+        time_from_registers(&registers)
+    }
+
+    /// Sets the calendar; only dates between 2000 and 2099 can be represented.
+    /// The clock is stopped while the registers are written and restarted
+    /// right after, so the new second starts counting from zero.
+    /// Clears VLF, as the time is valid again, and marks the record stored
+    /// in the user RAM (if any) as holding a trusted time.
+    pub fn set_time(&mut self, date_time: DateTime) -> Result<(), Error<E>> {
+        let time_registers = encode_time(&date_time).ok_or(Error::UnsupportedDate)?;
+        self.write_time_registers(&time_registers)
+    }
 
-    fn write_register(self: &mut Self, reg: u8, data: u8) -> Result<(), E> {
-        self.i2c.write(self.address, &[reg, data])
+    /// STOP is only held for the calendar burst, the bookkeeping runs
+    /// with the clock already restarted
+    fn write_time_registers(&mut self, time_registers: &[u8; 7]) -> Result<(), Error<E>> {
+        let control_register = self.stop_and_write_time(time_registers)?;
+        self.release_stop(control_register)?;
+        self.time_set()
     }
 
-    fn write_registers<const N: usize>(self: &mut Self, reg: u8, data: &[u8; N]) -> Result<(), E> {
-        for i in 0..N {
-            self.write_register(reg+(i as u8), data[i])?;
+    /// Like `write_time_registers`, but does the bookkeeping before returning
+    /// with STOP still set, so that releasing it is the only step left.
+    /// Returns the Control value that restarts the clock.
+    fn write_time_stopped(&mut self, time_registers: &[u8; 7]) -> Result<u8, Error<E>> {
+        let control_register = self.stop_and_write_time(time_registers)?;
+        self.time_set()?;
+        Ok(control_register)
+    }
+
+    /// Sets STOP and burst-writes already encoded SEC..YEAR registers.
+    /// Returns the Control value that restarts the clock, so that releasing
+    /// STOP takes a single write.
+    fn stop_and_write_time(&mut self, time_registers: &[u8; 7]) -> Result<u8, Error<E>> {
+        let control_register = self.read_register(REGISTER_CONTROL)?;
+        self.write_register(
+            REGISTER_CONTROL,
+            control_register | BIT_REGISTER_CONTROL_STOP,
+        )?;
+        self.write_registers(REGISTER_SEC, time_registers)?;
+        Ok(released_control(control_register))
+    }
+
+    /// `set_time` bookkeeping: clears VLF and marks the stored record as
+    /// holding a trusted time
+    fn time_set(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_VLF)?;
+        self.set_time_trusted(true)
+    }
+
+    fn release_stop(&mut self, control_register: u8) -> Result<(), Error<E>> {
+        self.write_register(REGISTER_CONTROL, control_register)
+    }
+
+    /// Clears the given flags without touching the others, so that events
+    /// raised in the meantime are not lost.
+    fn clear_flags(&mut self, flags: u8) -> Result<(), Error<E>> {
+        self.write_register(REGISTER_FLAG, MASK_REGISTER_FLAG & (!flags))
+    }
+
+    /// Read-modify-write: replaces the bits in `mask` with those in `value`
+    fn modify_register(&mut self, reg: u8, mask: u8, value: u8) -> Result<(), Error<E>> {
+        let register = self.read_register(reg)?;
+        self.write_register(reg, replace_bits(register, mask, value))
+    }
+
+    /// Applies `(register, mask, value)` steps in order, see `modify_register`
+    fn modify_registers(&mut self, steps: &[(u8, u8, u8)]) -> Result<(), Error<E>> {
+        for &(reg, mask, value) in steps {
+            self.modify_register(reg, mask, value)?;
         }
         Ok(())
+    }
 
-        /*
-        let buffer: [u8; N+1] = [0;N+1];
-        buffer[0] = reg;
-        for i in 1..N+1 {
-            buffer[i] = data[i-1];
-        }
-        self.i2c.write(self.address, buffer)
-        */
+    fn write_register(&mut self, reg: u8, data: u8) -> Result<(), Error<E>> {
+        self.i2c
+            .write(self.address, &[reg, data])
+            .map_err(Error::I2c)
+    }
+
+    /// Writes consecutive registers in a single auto-incrementing transaction,
+    /// so that e.g. the time cannot roll over half-way through
+    fn write_registers(&mut self, reg: u8, data: &[u8]) -> Result<(), Error<E>> {
+        let buffer = burst_buffer(reg, data)?;
+        self.i2c
+            .write(self.address, &buffer[..data.len() + 1])
+            .map_err(Error::I2c)
     }
 
-    fn read_register(self: &mut Self, reg: u8) -> Result<u8, E> {
+    fn read_register(&mut self, reg: u8) -> Result<u8, Error<E>> {
         self.read_registers::<1>(reg).map(|regs| regs[0])
     }
 
-    fn read_registers<const N: usize>(self: &mut Self, reg: u8) -> Result<[u8; N], E> {
+    fn read_registers<const N: usize>(&mut self, reg: u8) -> Result<[u8; N], Error<E>> {
         let mut buf: [u8; N] = [0; N];
-        self.i2c.write_read(self.address, &[reg], &mut buf)?;
+        self.read_registers_into(reg, &mut buf)?;
         Ok(buf)
     }
+
+    fn read_registers_into(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Error<E>> {
+        self.i2c
+            .write_read(self.address, &[reg], buf)
+            .map_err(Error::I2c)
+    }
+}
+
+/// Control with TEST cleared, `None` if it is already clear
+fn clear_test(control_register: u8) -> Option<u8> {
+    if (control_register & BIT_REGISTER_CONTROL_TEST) > 0 {
+        Some(control_register & !BIT_REGISTER_CONTROL_TEST)
+    } else {
+        None
+    }
+}
+
+/// Replaces the bits in `mask` with those in `value`
+fn replace_bits(register: u8, mask: u8, value: u8) -> u8 {
+    (register & (!mask)) | (value & mask)
+}
+
+/// Control value that restarts a clock stopped by `set_time`
+fn released_control(control_register: u8) -> u8 {
+    control_register & !(BIT_REGISTER_CONTROL_STOP | BIT_REGISTER_CONTROL_TEST)
+}
+
+/// Register address followed by `data`, as sent by a burst write
+fn burst_buffer<E>(reg: u8, data: &[u8]) -> Result<[u8; MAX_BURST_LEN + 1], Error<E>> {
+    if data.len() > MAX_BURST_LEN {
+        return Err(Error::OutOfBounds);
+    }
+    let mut buffer = [0; MAX_BURST_LEN + 1];
+    buffer[0] = reg;
+    buffer[1..data.len() + 1].copy_from_slice(data);
+    Ok(buffer)
+}
+
+/// Checks the SEC..CONTROL registers and decodes the calendar
+fn time_from_registers<E>(registers: &[u8; TIME_READ_SIZE]) -> Result<DateTime, Error<E>> {
+    check_time_validity(registers)?;
+    decode_time(&registers[..7]).ok_or(Error::InvalidRegister)
+}
+
+/// Fails if the flags in the SEC..CONTROL registers say the time is not valid
+fn check_time_validity<E>(registers: &[u8; TIME_READ_SIZE]) -> Result<(), Error<E>> {
+    let flag_register = registers[(REGISTER_FLAG - REGISTER_SEC) as usize];
+    let control_register = registers[(REGISTER_CONTROL - REGISTER_SEC) as usize];
+
+    match TimeValidity::from_registers(flag_register, control_register) {
+        TimeValidity::VoltageLow => Err(Error::VoltageLow),
+        TimeValidity::Stopped => Err(Error::ClockStopped),
+        TimeValidity::Valid => Ok(()),
+    }
 }
 
-fn bcd2bin(bcd: u8) -> u8 {
-    ((bcd >> 4) & 0xF) * 10 + ((bcd) & 0xF)
+/// Encodes a date and time into the SEC..YEAR registers
+fn encode_time(date_time: &DateTime) -> Option<[u8; 7]> {
+    if !(CENTURY..CENTURY + 100).contains(&date_time.year()) {
+        return None;
+    }
+
+    Some([
+        bin2bcd(date_time.second()),
+        bin2bcd(date_time.minute()),
+        bin2bcd(date_time.hour()),
+        1 << date_time.weekday().number_from_sunday(),
+        bin2bcd(date_time.day()),
+        bin2bcd(date_time.month()),
+        bin2bcd((date_time.year() - CENTURY) as u8),
+    ])
+}
+
+/// Decodes the SEC..YEAR registers, `None` if they do not hold a valid date
+fn decode_time(time_registers: &[u8]) -> Option<DateTime> {
+    let sec = bcd2bin(time_registers[0])?;
+    let min = bcd2bin(time_registers[1])?;
+    let hour = bcd2bin(time_registers[2])?;
+    // The weekday is redundant with the date, but must be one-hot
+    if time_registers[3].count_ones() != 1 || time_registers[3] > 0x40 {
+        return None;
+    }
+    let day = bcd2bin(time_registers[4])?;
+    let month = bcd2bin(time_registers[5])?;
+    let year = bcd2bin(time_registers[6])?;
+
+    DateTime::new(CENTURY + year as u16, month, day, hour, min, sec)
+}
+
+/// `None` if either digit is not a decimal one
+fn bcd2bin(bcd: u8) -> Option<u8> {
+    let (tens, units) = (bcd >> 4, bcd & 0xF);
+    if tens > 9 || units > 9 {
+        return None;
+    }
+    Some(tens * 10 + units)
 }
 
 fn bin2bcd(bin: u8) -> u8 {
     (((bin) / 10) << 4) | ((bin) % 10)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::{Register, BIT_REGISTER_CONTROL_AIE, INIT_REGISTERS};
+    use crate::sim::testing::{date_time, running_at, NoDelay};
+    use crate::sim::Rx8010sjSim;
+
+    #[test]
+    fn encode_time_bcd_and_weekday() {
+        let date_time = DateTime::new(2024, 2, 29, 13, 5, 9).unwrap();
+        // 2024-02-29 is a Thursday, bit 4 of WEEK
+        assert_eq!(
+            encode_time(&date_time),
+            Some([0x09, 0x05, 0x13, 0x10, 0x29, 0x02, 0x24])
+        );
+    }
+
+    #[test]
+    fn encode_time_out_of_range() {
+        assert_eq!(
+            encode_time(&DateTime::new(1999, 12, 31, 23, 59, 59).unwrap()),
+            None
+        );
+        assert_eq!(
+            encode_time(&DateTime::new(2100, 1, 1, 0, 0, 0).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_time_round_trip() {
+        for date_time in [
+            DateTime::new(2000, 1, 1, 0, 0, 0).unwrap(),
+            DateTime::new(2024, 2, 29, 13, 5, 9).unwrap(),
+            DateTime::new(2099, 12, 31, 23, 59, 59).unwrap(),
+        ] {
+            let registers = encode_time(&date_time).unwrap();
+            assert_eq!(decode_time(&registers), Some(date_time));
+        }
+    }
+
+    #[test]
+    fn decode_time_rejects_invalid_registers() {
+        let valid = [0x09, 0x05, 0x13, 0x10, 0x29, 0x02, 0x24];
+        assert!(decode_time(&valid).is_some());
+
+        let invalid = [
+            // Not BCD
+            (0, 0x0A),
+            // 60 seconds
+            (0, 0x60),
+            (2, 0x24),
+            // WEEK not one-hot
+            (3, 0x00),
+            (3, 0x11),
+            (3, 0x80),
+            // 2024-02-30
+            (4, 0x30),
+            (5, 0x13),
+        ];
+        for (index, value) in invalid {
+            let mut registers = valid;
+            registers[index] = value;
+            assert_eq!(decode_time(&registers), None, "{index}: {value:#04x}");
+        }
+    }
+
+    #[test]
+    fn bcd_conversions() {
+        for bin in 0..100 {
+            assert_eq!(bcd2bin(bin2bcd(bin)), Some(bin));
+        }
+        assert_eq!(bcd2bin(0x1A), None);
+        assert_eq!(bcd2bin(0xA1), None);
+    }
+
+    #[test]
+    fn set_time_get_time_round_trip() {
+        let time = date_time(2024, 2, 29, 13, 5, 9);
+        let mut sim = running_at(time);
+        assert_eq!(Rx8010sj::new(&mut sim).get_time(), Ok(time));
+
+        sim.advance(core::time::Duration::from_millis(1500));
+        assert_eq!(
+            Rx8010sj::new(&mut sim).get_time(),
+            Ok(date_time(2024, 2, 29, 13, 5, 10))
+        );
+    }
+
+    #[test]
+    fn set_time_century_rollover() {
+        let mut sim = running_at(date_time(2099, 12, 31, 23, 59, 59));
+        sim.advance(core::time::Duration::from_secs(1));
+        assert_eq!(
+            Rx8010sj::new(&mut sim).get_time(),
+            Ok(date_time(2000, 1, 1, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn set_time_rejects_unsupported_years() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(
+            rtc.set_time(date_time(2100, 1, 1, 0, 0, 0)),
+            Err(Error::UnsupportedDate)
+        );
+        assert_eq!(rtc.get_time(), Ok(date_time(2024, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn init_cold_start_resets_registers() {
+        let mut sim = Rx8010sjSim::new();
+        sim.set_register(Register::EXTENSION, 0xFF);
+        sim.set_register(Register::CONTROL, 0xBC);
+        assert_eq!(
+            Rx8010sj::new(&mut sim).init(&mut NoDelay),
+            Ok(InitStatus::ColdStart)
+        );
+        for (reg, value) in INIT_REGISTERS {
+            assert_eq!(sim.register(Register::new(reg).unwrap()), value);
+        }
+        assert_eq!(sim.register(Register::EXTENSION), 0x00);
+        assert_eq!(sim.register(Register::FLAG), BIT_REGISTER_FLAG_VLF);
+        assert_eq!(sim.register(Register::CONTROL), 0x00);
+    }
+
+    #[test]
+    fn init_warm_start_only_clears_test() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let control = BIT_REGISTER_CONTROL_TEST | BIT_REGISTER_CONTROL_AIE;
+        sim.set_register(Register::CONTROL, control);
+        sim.set_register(Register::EXTENSION, 0x40);
+        assert_eq!(
+            Rx8010sj::new(&mut sim).init(&mut NoDelay),
+            Ok(InitStatus::TimeRetained)
+        );
+        assert_eq!(sim.register(Register::CONTROL), BIT_REGISTER_CONTROL_AIE);
+        assert_eq!(sim.register(Register::EXTENSION), 0x40);
+    }
+
+    #[test]
+    fn voltage_low_blocks_get_time_until_set_time() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.init(&mut NoDelay).unwrap();
+        assert_eq!(rtc.is_voltage_low(), Ok(true));
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::VoltageLow));
+        assert_eq!(rtc.get_time(), Err(Error::VoltageLow));
+
+        rtc.set_time(date_time(2024, 1, 1, 0, 0, 0)).unwrap();
+        assert_eq!(rtc.is_voltage_low(), Ok(false));
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Valid));
+
+        sim.set_voltage_low();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.get_time(), Err(Error::VoltageLow));
+        rtc.clear_voltage_low().unwrap();
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Valid));
+    }
+
+    #[test]
+    fn stopped_clock_is_reported() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_stopped(true).unwrap();
+        assert_eq!(rtc.is_stopped(), Ok(true));
+        assert_eq!(rtc.time_validity(), Ok(TimeValidity::Stopped));
+        assert_eq!(rtc.get_time(), Err(Error::ClockStopped));
+
+        rtc.set_stopped(false).unwrap();
+        assert_eq!(rtc.get_time(), Ok(date_time(2024, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn burst_buffer_bounds() {
+        let data: [u8; MAX_BURST_LEN] = core::array::from_fn(|i| i as u8);
+        let buffer = burst_buffer::<()>(REGISTER_SEC, &data).unwrap();
+        assert_eq!(buffer[0], REGISTER_SEC);
+        assert_eq!(buffer[1..], data);
+        assert_eq!(
+            burst_buffer::<()>(REGISTER_SEC, &[0; MAX_BURST_LEN + 1]),
+            Err(Error::OutOfBounds)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use embedded_hal::i2c::I2c;
+
+use crate::registers::REGISTER_RAM;
+use crate::{Error, Rx8010sj};
+
+/// Size of the battery-backed user RAM (registers 0x20-0x2F)
+pub const RAM_SIZE: usize = 16;
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Reads `buf.len()` bytes of user RAM starting at `offset`, in a single burst
+    pub fn read_ram(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), Error<E>> {
+        match ram_register(offset, buf.len())? {
+            Some(reg) => self.read_registers_into(reg, buf),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes `data` to the user RAM starting at `offset`, in a single burst
+    pub fn write_ram(&mut self, offset: usize, data: &[u8]) -> Result<(), Error<E>> {
+        match ram_register(offset, data.len())? {
+            Some(reg) => self.write_registers(reg, data),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Register address of `offset`, checking that `len` bytes fit in the RAM.
+/// `None` for an empty access, which needs no bus transfer.
+pub(crate) fn ram_register<E>(offset: usize, len: usize) -> Result<Option<u8>, Error<E>> {
+    match offset.checked_add(len) {
+        Some(_) if len == 0 => Ok(None),
+        Some(end) if end <= RAM_SIZE => Ok(Some(REGISTER_RAM + offset as u8)),
+        _ => Err(Error::OutOfBounds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use crate::sim::Rx8010sjSim;
+
+    #[test]
+    fn read_write_round_trip() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let data: [u8; RAM_SIZE] = core::array::from_fn(|i| i as u8 + 1);
+        rtc.write_ram(0, &data).unwrap();
+        let mut buf = [0; RAM_SIZE];
+        rtc.read_ram(0, &mut buf).unwrap();
+        assert_eq!(buf, data);
+
+        let mut buf = [0; 3];
+        rtc.read_ram(13, &mut buf).unwrap();
+        assert_eq!(buf, [14, 15, 16]);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.read_ram(14, &mut [0; 3]), Err(Error::OutOfBounds));
+        assert_eq!(rtc.write_ram(14, &[0; 3]), Err(Error::OutOfBounds));
+        assert_eq!(rtc.write_ram(usize::MAX, &[0]), Err(Error::OutOfBounds));
+        assert_eq!(rtc.write_ram(RAM_SIZE, &[]), Ok(()));
+
+        // Nothing spilled over the RAM
+        assert_eq!(sim.register(Register::RESERVED_30), 0x00);
+    }
+}
@@ -0,0 +1,228 @@
+use embedded_hal::i2c::I2c;
+
+use crate::{Error, Rx8010sj, TimeValidity, RAM_SIZE};
+
+/// "RX", marks the RAM as holding a record
+const RECORD_MAGIC: [u8; 2] = [0x52, 0x58];
+const RECORD_FLAG_TIME_TRUSTED: u8 = 0x01;
+
+const OFFSET_VERSION: usize = 2;
+const OFFSET_FLAGS: usize = 3;
+const OFFSET_PAYLOAD: usize = 4;
+const OFFSET_CRC: usize = OFFSET_PAYLOAD + RECORD_PAYLOAD_SIZE;
+
+/// Application bytes held by a record:
+/// magic (2) + version (1) + flags (1) + payload + CRC-16 (2) fill the RAM
+pub const RECORD_PAYLOAD_SIZE: usize = 10;
+
+/// Versioned record stored in the user RAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record {
+    pub version: u8,
+    pub payload: [u8; RECORD_PAYLOAD_SIZE],
+    /// Set by `set_time`, cleared when the clock is stopped or loses power
+    pub time_trusted: bool,
+}
+
+/// Outcome of `load_record`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordStatus {
+    /// The RAM does not start with the record magic
+    Absent,
+    /// The magic is there, but the CRC does not match
+    Corrupt,
+    Valid(Record),
+}
+
+impl Record {
+    pub(crate) fn to_bytes(self) -> [u8; RAM_SIZE] {
+        let mut bytes = [0; RAM_SIZE];
+        bytes[..OFFSET_VERSION].copy_from_slice(&RECORD_MAGIC);
+        bytes[OFFSET_VERSION] = self.version;
+        bytes[OFFSET_FLAGS] = if self.time_trusted {
+            RECORD_FLAG_TIME_TRUSTED
+        } else {
+            0
+        };
+        bytes[OFFSET_PAYLOAD..OFFSET_CRC].copy_from_slice(&self.payload);
+        let crc = crc16(&bytes[..OFFSET_CRC]);
+        bytes[OFFSET_CRC..].copy_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+
+    /// New RAM contents with the time marker set to `trusted`, `None` if
+    /// there is no valid record or the marker already has that value
+    pub(crate) fn with_time_trusted(
+        bytes: &[u8; RAM_SIZE],
+        trusted: bool,
+    ) -> Option<[u8; RAM_SIZE]> {
+        match Record::from_bytes(bytes) {
+            RecordStatus::Valid(record) if record.time_trusted != trusted => Some(
+                Record {
+                    time_trusted: trusted,
+                    ..record
+                }
+                .to_bytes(),
+            ),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8; RAM_SIZE]) -> RecordStatus {
+        if bytes[..OFFSET_VERSION] != RECORD_MAGIC {
+            return RecordStatus::Absent;
+        }
+        if crc16(&bytes[..OFFSET_CRC]).to_be_bytes() != bytes[OFFSET_CRC..] {
+            return RecordStatus::Corrupt;
+        }
+
+        let mut payload = [0; RECORD_PAYLOAD_SIZE];
+        payload.copy_from_slice(&bytes[OFFSET_PAYLOAD..OFFSET_CRC]);
+        RecordStatus::Valid(Record {
+            version: bytes[OFFSET_VERSION],
+            payload,
+            time_trusted: (bytes[OFFSET_FLAGS] & RECORD_FLAG_TIME_TRUSTED) > 0,
+        })
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Stores a record, taking up the whole user RAM.
+    /// The time is marked as trusted if the clock is currently valid.
+    pub fn store_record(
+        &mut self,
+        version: u8,
+        payload: &[u8; RECORD_PAYLOAD_SIZE],
+    ) -> Result<(), Error<E>> {
+        let record = Record {
+            version,
+            payload: *payload,
+            time_trusted: self.time_validity()? == TimeValidity::Valid,
+        };
+        self.write_ram(0, &record.to_bytes())
+    }
+
+    pub fn load_record(&mut self) -> Result<RecordStatus, Error<E>> {
+        let mut bytes = [0; RAM_SIZE];
+        self.read_ram(0, &mut bytes)?;
+        Ok(Record::from_bytes(&bytes))
+    }
+
+    /// True if a valid record marks the time as trusted and the chip has
+    /// neither stopped nor lost power since. The marker is latched off as
+    /// soon as the clock is found stopped or VLF set, so that only
+    /// `set_time` can restore it.
+    pub fn is_time_trusted(&mut self) -> Result<bool, Error<E>> {
+        match self.load_record()? {
+            RecordStatus::Valid(record) if record.time_trusted => {
+                if self.time_validity()? == TimeValidity::Valid {
+                    Ok(true)
+                } else {
+                    self.set_time_trusted(false)?;
+                    Ok(false)
+                }
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Sets the time marker of a stored record: `set_time` marks it as
+    /// trusted, anything that makes the time unreliable clears it.
+    /// RAM without a valid record is left untouched.
+    pub(crate) fn set_time_trusted(&mut self, trusted: bool) -> Result<(), Error<E>> {
+        let mut bytes = [0; RAM_SIZE];
+        self.read_ram(0, &mut bytes)?;
+        if let Some(bytes) = Record::with_time_trusted(&bytes, trusted) {
+            self.write_ram(0, &bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// CRC-16/CCITT-FALSE
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+            if (crc & 0x8000) > 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    fn record(time_trusted: bool) -> Record {
+        Record {
+            version: 3,
+            payload: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            time_trusted,
+        }
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        for time_trusted in [false, true] {
+            let bytes = record(time_trusted).to_bytes();
+            assert_eq!(bytes[..2], RECORD_MAGIC);
+            assert_eq!(
+                Record::from_bytes(&bytes),
+                RecordStatus::Valid(record(time_trusted))
+            );
+        }
+    }
+
+    #[test]
+    fn from_bytes_detects_absent_and_corrupt() {
+        assert_eq!(Record::from_bytes(&[0; RAM_SIZE]), RecordStatus::Absent);
+
+        let mut bytes = record(false).to_bytes();
+        bytes[OFFSET_PAYLOAD] ^= 0x01;
+        assert_eq!(Record::from_bytes(&bytes), RecordStatus::Corrupt);
+    }
+
+    #[test]
+    fn with_time_trusted() {
+        let bytes = record(false).to_bytes();
+        let trusted = Record::with_time_trusted(&bytes, true).unwrap();
+        assert_eq!(
+            Record::from_bytes(&trusted),
+            RecordStatus::Valid(record(true))
+        );
+        assert_eq!(Record::with_time_trusted(&trusted, true), None);
+        assert_eq!(Record::with_time_trusted(&trusted, false), Some(bytes));
+        assert_eq!(Record::with_time_trusted(&[0; RAM_SIZE], true), None);
+    }
+
+    #[test]
+    fn trusted_time_is_latched_off() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.store_record(1, &[0; RECORD_PAYLOAD_SIZE]).unwrap();
+        assert_eq!(rtc.is_time_trusted(), Ok(true));
+
+        rtc.set_stopped(true).unwrap();
+        rtc.set_stopped(false).unwrap();
+        assert_eq!(rtc.is_time_trusted(), Ok(false));
+
+        rtc.set_time(date_time(2024, 3, 1, 7, 0, 0)).unwrap();
+        assert_eq!(rtc.is_time_trusted(), Ok(true));
+        sim.set_voltage_low();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.clear_voltage_low().unwrap();
+        assert_eq!(rtc.is_time_trusted(), Ok(false));
+    }
+}
@@ -0,0 +1,440 @@
+//! RX-8010SJ register map (0x10-0x32) and typed views of the control registers.
+//!
+//! The high-level API covers most features; these types, together with
+//! `Rx8010sj::read_raw`/`write_raw` and `read_bitfield`/`write_bitfield`,
+//! give access to everything else.
+
+use embedded_hal::i2c::I2c;
+
+use crate::{CompensationInterval, Error, FoutFrequency, Rx8010sj, TimerClock, UpdateInterval};
+
+pub(crate) const REGISTER_SEC: u8 = 0x10;
+pub(crate) const REGISTER_MIN: u8 = 0x11;
+pub(crate) const REGISTER_HOUR: u8 = 0x12;
+pub(crate) const REGISTER_WEEK: u8 = 0x13;
+pub(crate) const REGISTER_DAY: u8 = 0x14;
+pub(crate) const REGISTER_MONTH: u8 = 0x15;
+pub(crate) const REGISTER_YEAR: u8 = 0x16;
+pub(crate) const REGISTER_RESERVED_17: u8 = 0x17;
+pub(crate) const REGISTER_ALARM_MIN: u8 = 0x18;
+pub(crate) const REGISTER_ALARM_HOUR: u8 = 0x19;
+pub(crate) const REGISTER_ALARM_WEEK_DAY: u8 = 0x1A;
+pub(crate) const REGISTER_TIMER_COUNTER_0: u8 = 0x1B;
+pub(crate) const REGISTER_TIMER_COUNTER_1: u8 = 0x1C;
+pub(crate) const REGISTER_EXTENSION: u8 = 0x1D;
+pub(crate) const REGISTER_FLAG: u8 = 0x1E;
+pub(crate) const REGISTER_CONTROL: u8 = 0x1F;
+pub(crate) const REGISTER_RAM: u8 = 0x20;
+pub(crate) const REGISTER_RESERVED_30: u8 = 0x30;
+pub(crate) const REGISTER_RESERVED_31: u8 = 0x31;
+pub(crate) const REGISTER_IRQ_CONTROL: u8 = 0x32;
+
+pub(crate) const MASK_REGISTER_EXTENSION_TSEL: u8 = 0x07;
+pub(crate) const BIT_REGISTER_EXTENSION_WADA: u8 = 0x08;
+pub(crate) const BIT_REGISTER_EXTENSION_TE: u8 = 0x10;
+pub(crate) const BIT_REGISTER_EXTENSION_USEL: u8 = 0x20;
+pub(crate) const MASK_REGISTER_EXTENSION_FSEL: u8 = 0xC0;
+
+pub(crate) const BIT_REGISTER_FLAG_VLF: u8 = 0x02;
+pub(crate) const BIT_REGISTER_FLAG_AF: u8 = 0x08;
+pub(crate) const BIT_REGISTER_FLAG_TF: u8 = 0x10;
+pub(crate) const BIT_REGISTER_FLAG_UF: u8 = 0x20;
+/// Flags are cleared by writing 0, writing 1 leaves them untouched
+pub(crate) const MASK_REGISTER_FLAG: u8 =
+    BIT_REGISTER_FLAG_VLF | BIT_REGISTER_FLAG_AF | BIT_REGISTER_FLAG_TF | BIT_REGISTER_FLAG_UF;
+
+pub(crate) const MASK_REGISTER_CONTROL_CSEL: u8 = 0x03;
+pub(crate) const BIT_REGISTER_CONTROL_TSTP: u8 = 0x04;
+pub(crate) const BIT_REGISTER_CONTROL_AIE: u8 = 0x08;
+pub(crate) const BIT_REGISTER_CONTROL_TIE: u8 = 0x10;
+pub(crate) const BIT_REGISTER_CONTROL_UIE: u8 = 0x20;
+pub(crate) const BIT_REGISTER_CONTROL_STOP: u8 = 0x40;
+pub(crate) const BIT_REGISTER_CONTROL_TEST: u8 = 0x80;
+
+pub(crate) const BIT_REGISTER_IRQ_CONTROL_FOPIN0: u8 = 0x01;
+pub(crate) const BIT_REGISTER_IRQ_CONTROL_FOPIN1: u8 = 0x02;
+pub(crate) const BIT_REGISTER_IRQ_CONTROL_TMPIN: u8 = 0x04;
+
+/// Set in an alarm register, the field is ignored when matching
+pub(crate) const BIT_ALARM_AE: u8 = 0x80;
+
+/// Values the datasheet requires in the reserved registers
+pub(crate) const RESERVED_REGISTERS: [(u8, u8); 3] = [
+    (REGISTER_RESERVED_17, 0xD8),
+    (REGISTER_RESERVED_30, 0x00),
+    (REGISTER_RESERVED_31, 0x08),
+];
+
+/// Written by `init`: the reserved registers, plus IRQ control back to its
+/// default pin routing
+pub(crate) const INIT_REGISTERS: [(u8, u8); 4] = [
+    RESERVED_REGISTERS[0],
+    RESERVED_REGISTERS[1],
+    RESERVED_REGISTERS[2],
+    (REGISTER_IRQ_CONTROL, 0x00),
+];
+
+/// Address of a documented register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Register(u8);
+
+impl Register {
+    pub const SEC: Register = Register(REGISTER_SEC);
+    pub const MIN: Register = Register(REGISTER_MIN);
+    pub const HOUR: Register = Register(REGISTER_HOUR);
+    pub const WEEK: Register = Register(REGISTER_WEEK);
+    pub const DAY: Register = Register(REGISTER_DAY);
+    pub const MONTH: Register = Register(REGISTER_MONTH);
+    pub const YEAR: Register = Register(REGISTER_YEAR);
+    pub const RESERVED_17: Register = Register(REGISTER_RESERVED_17);
+    pub const ALARM_MIN: Register = Register(REGISTER_ALARM_MIN);
+    pub const ALARM_HOUR: Register = Register(REGISTER_ALARM_HOUR);
+    pub const ALARM_WEEK_DAY: Register = Register(REGISTER_ALARM_WEEK_DAY);
+    pub const TIMER_COUNTER_0: Register = Register(REGISTER_TIMER_COUNTER_0);
+    pub const TIMER_COUNTER_1: Register = Register(REGISTER_TIMER_COUNTER_1);
+    pub const EXTENSION: Register = Register(REGISTER_EXTENSION);
+    pub const FLAG: Register = Register(REGISTER_FLAG);
+    pub const CONTROL: Register = Register(REGISTER_CONTROL);
+    /// First byte of the user RAM, see `Register::ram`
+    pub const RAM: Register = Register(REGISTER_RAM);
+    pub const RESERVED_30: Register = Register(REGISTER_RESERVED_30);
+    pub const RESERVED_31: Register = Register(REGISTER_RESERVED_31);
+    pub const IRQ_CONTROL: Register = Register(REGISTER_IRQ_CONTROL);
+
+    /// First and last documented addresses
+    pub const FIRST: Register = Register::SEC;
+    pub const LAST: Register = Register::IRQ_CONTROL;
+
+    /// `None` if the address is outside of the documented map
+    pub const fn new(address: u8) -> Option<Register> {
+        if address >= REGISTER_SEC && address <= REGISTER_IRQ_CONTROL {
+            Some(Register(address))
+        } else {
+            None
+        }
+    }
+
+    /// User RAM byte at `offset` (0-15)
+    pub const fn ram(offset: u8) -> Option<Register> {
+        if (offset as usize) < crate::RAM_SIZE {
+            Some(Register(REGISTER_RAM + offset))
+        } else {
+            None
+        }
+    }
+
+    pub const fn address(self) -> u8 {
+        self.0
+    }
+
+    /// Required value of a reserved register, `None` for the others
+    pub fn reserved_value(self) -> Option<u8> {
+        RESERVED_REGISTERS
+            .iter()
+            .find(|(reg, _)| *reg == self.0)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// A register with a typed bitfield view
+pub trait BitfieldRegister: Copy {
+    const REGISTER: Register;
+
+    fn from_bits(bits: u8) -> Self;
+    fn bits(self) -> u8;
+}
+
+macro_rules! bitfield_register {
+    ($(#[$doc:meta])* $name:ident, $register:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct $name(pub u8);
+
+        impl BitfieldRegister for $name {
+            const REGISTER: Register = $register;
+
+            fn from_bits(bits: u8) -> Self {
+                $name(bits)
+            }
+
+            fn bits(self) -> u8 {
+                self.0
+            }
+        }
+    };
+}
+
+macro_rules! bit {
+    ($(#[$doc:meta])* $get:ident, $set:ident, $mask:expr) => {
+        $(#[$doc])*
+        pub fn $get(self) -> bool {
+            (self.0 & $mask) > 0
+        }
+
+        pub fn $set(&mut self, value: bool) {
+            if value {
+                self.0 |= $mask;
+            } else {
+                self.0 &= !$mask;
+            }
+        }
+    };
+}
+
+bitfield_register!(
+    /// Extension register (0x1D)
+    Extension,
+    Register::EXTENSION
+);
+
+impl Extension {
+    bit!(
+        /// TE: countdown timer enabled
+        timer_enabled,
+        set_timer_enabled,
+        BIT_REGISTER_EXTENSION_TE
+    );
+    bit!(
+        /// WADA: the third alarm register holds a day of the month, not weekdays
+        wada,
+        set_wada,
+        BIT_REGISTER_EXTENSION_WADA
+    );
+
+    /// TSEL
+    pub fn timer_clock(self) -> TimerClock {
+        TimerClock::from_bits(self.0)
+    }
+
+    pub fn set_timer_clock(&mut self, clock: TimerClock) {
+        self.0 = (self.0 & !MASK_REGISTER_EXTENSION_TSEL) | clock.bits();
+    }
+
+    /// USEL
+    pub fn update_interval(self) -> UpdateInterval {
+        UpdateInterval::from_bits(self.0)
+    }
+
+    pub fn set_update_interval(&mut self, interval: UpdateInterval) {
+        self.0 = (self.0 & !BIT_REGISTER_EXTENSION_USEL) | interval.bits();
+    }
+
+    /// FSEL
+    pub fn fout_frequency(self) -> FoutFrequency {
+        FoutFrequency::from_bits(self.0)
+    }
+
+    pub fn set_fout_frequency(&mut self, frequency: FoutFrequency) {
+        self.0 = (self.0 & !MASK_REGISTER_EXTENSION_FSEL) | frequency.bits();
+    }
+}
+
+bitfield_register!(
+    /// Flag register (0x1E); writing 0 to a flag clears it, writing 1 has no effect
+    Flag,
+    Register::FLAG
+);
+
+impl Flag {
+    bit!(
+        /// VLF: voltage low, the time is invalid
+        vlf,
+        set_vlf,
+        BIT_REGISTER_FLAG_VLF
+    );
+    bit!(
+        /// AF: alarm matched
+        af,
+        set_af,
+        BIT_REGISTER_FLAG_AF
+    );
+    bit!(
+        /// TF: countdown timer expired
+        tf,
+        set_tf,
+        BIT_REGISTER_FLAG_TF
+    );
+    bit!(
+        /// UF: time update
+        uf,
+        set_uf,
+        BIT_REGISTER_FLAG_UF
+    );
+}
+
+bitfield_register!(
+    /// Control register (0x1F)
+    Control,
+    Register::CONTROL
+);
+
+impl Control {
+    bit!(
+        /// TSTP: countdown timer paused
+        tstp,
+        set_tstp,
+        BIT_REGISTER_CONTROL_TSTP
+    );
+    bit!(
+        /// AIE: alarm interrupt enabled
+        aie,
+        set_aie,
+        BIT_REGISTER_CONTROL_AIE
+    );
+    bit!(
+        /// TIE: timer interrupt enabled
+        tie,
+        set_tie,
+        BIT_REGISTER_CONTROL_TIE
+    );
+    bit!(
+        /// UIE: update interrupt enabled
+        uie,
+        set_uie,
+        BIT_REGISTER_CONTROL_UIE
+    );
+    bit!(
+        /// STOP: the clock is stopped
+        stop,
+        set_stop,
+        BIT_REGISTER_CONTROL_STOP
+    );
+    bit!(
+        /// TEST: factory test mode, must be 0
+        test,
+        set_test,
+        BIT_REGISTER_CONTROL_TEST
+    );
+
+    /// CSEL
+    pub fn compensation_interval(self) -> CompensationInterval {
+        CompensationInterval::from_bits(self.0)
+    }
+
+    pub fn set_compensation_interval(&mut self, interval: CompensationInterval) {
+        self.0 = (self.0 & !MASK_REGISTER_CONTROL_CSEL) | interval.bits();
+    }
+}
+
+bitfield_register!(
+    /// IRQ control register (0x32)
+    IrqControl,
+    Register::IRQ_CONTROL
+);
+
+impl IrqControl {
+    bit!(
+        /// FOPIN0: FOUT output pin selection, bit 0
+        fopin0,
+        set_fopin0,
+        BIT_REGISTER_IRQ_CONTROL_FOPIN0
+    );
+    bit!(
+        /// FOPIN1: FOUT output pin selection, bit 1
+        fopin1,
+        set_fopin1,
+        BIT_REGISTER_IRQ_CONTROL_FOPIN1
+    );
+    bit!(
+        /// TMPIN: timer interrupt output pin selection
+        tmpin,
+        set_tmpin,
+        BIT_REGISTER_IRQ_CONTROL_TMPIN
+    );
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Reads a single register
+    pub fn read_raw(&mut self, register: Register) -> Result<u8, Error<E>> {
+        self.read_register(register.address())
+    }
+
+    /// Writes a single register, bypassing any check of the high-level API
+    pub fn write_raw(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
+        self.write_register(register.address(), value)
+    }
+
+    /// Burst-reads consecutive registers starting at `start`; fails with
+    /// `OutOfBounds` if the read would go past the end of the map.
+    pub fn read_raw_burst(&mut self, start: Register, buf: &mut [u8]) -> Result<(), Error<E>> {
+        let end = start.address() as usize + buf.len();
+        if end > Register::LAST.address() as usize + 1 {
+            return Err(Error::OutOfBounds);
+        }
+        if buf.is_empty() {
+            return Ok(());
+        }
+        self.read_registers_into(start.address(), buf)
+    }
+
+    /// Burst-writes consecutive registers starting at `start` in a single
+    /// transaction; fails with `OutOfBounds` past the end of the map.
+    pub fn write_raw_burst(&mut self, start: Register, data: &[u8]) -> Result<(), Error<E>> {
+        let end = start.address() as usize + data.len();
+        if end > Register::LAST.address() as usize + 1 {
+            return Err(Error::OutOfBounds);
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.write_registers(start.address(), data)
+    }
+
+    pub fn read_bitfield<R: BitfieldRegister>(&mut self) -> Result<R, Error<E>> {
+        self.read_register(R::REGISTER.address()).map(R::from_bits)
+    }
+
+    pub fn write_bitfield<R: BitfieldRegister>(&mut self, value: R) -> Result<(), Error<E>> {
+        self.write_register(R::REGISTER.address(), value.bits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::Rx8010sjSim;
+
+    #[test]
+    fn register_map_bounds() {
+        assert_eq!(Register::new(0x0F), None);
+        assert_eq!(Register::new(0x32), Some(Register::IRQ_CONTROL));
+        assert_eq!(Register::new(0x33), None);
+        assert_eq!(Register::ram(15), Some(Register::new(0x2F).unwrap()));
+        assert_eq!(Register::ram(16), None);
+    }
+
+    #[test]
+    fn raw_bursts_stay_within_the_map() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(
+            rtc.read_raw_burst(Register::IRQ_CONTROL, &mut [0; 2]),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(
+            rtc.write_raw_burst(Register::RESERVED_31, &[0; 3]),
+            Err(Error::OutOfBounds)
+        );
+        assert_eq!(rtc.read_raw_burst(Register::IRQ_CONTROL, &mut []), Ok(()));
+
+        rtc.write_raw_burst(Register::RESERVED_31, &[0x08, 0x04])
+            .unwrap();
+        let mut registers = [0; 0x23];
+        rtc.read_raw_burst(Register::FIRST, &mut registers).unwrap();
+        assert_eq!(registers[0x22], 0x04);
+        assert_eq!(sim.register(Register::IRQ_CONTROL), 0x04);
+    }
+
+    #[test]
+    fn bitfield_access() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.write_bitfield(Control::from_bits(BIT_REGISTER_CONTROL_STOP))
+            .unwrap();
+        assert_eq!(rtc.read_bitfield::<Control>().map(Control::bits), Ok(0x40));
+        assert_eq!(rtc.read_raw(Register::CONTROL), Ok(0x40));
+    }
+}
@@ -0,0 +1,237 @@
+//! `rtcc` traits on top of the chrono conversions.
+//! Note that the inherent `Rx8010sj::set_time` shadows `Rtcc::set_time`:
+//! call the latter as `Rtcc::set_time(&mut rtc, &time)`.
+
+use embedded_hal::i2c::I2c;
+use rtcc::{DateTimeAccess, Datelike, Hours, NaiveDate, NaiveDateTime, NaiveTime, Rtcc, Timelike};
+
+use crate::registers::{REGISTER_SEC, REGISTER_WEEK};
+use crate::{check_time_validity, DateTime, Error, Rx8010sj, TIME_READ_SIZE};
+
+impl<I2C, E> DateTimeAccess for Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E>;
+
+    fn datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        self.get_naive_datetime()
+    }
+
+    fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Error<E>> {
+        self.set_naive_datetime(datetime)
+    }
+}
+
+/// Getters fail like `get_time`. Setters replace a single field of the
+/// current time and write it back with `set_time`, keeping the weekday in
+/// line with the date; they fail like `get_time` too, as one field is not
+/// enough to make a lost time valid again (use `set_datetime` for that).
+/// Fields that would make an invalid date fail with `InvalidArgument`.
+impl<I2C, E> Rtcc for Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    fn seconds(&mut self) -> Result<u8, Error<E>> {
+        self.get_time().map(|date_time| date_time.second())
+    }
+
+    fn minutes(&mut self) -> Result<u8, Error<E>> {
+        self.get_time().map(|date_time| date_time.minute())
+    }
+
+    /// Always `Hours::H24`, the RTC has no 12-hour mode
+    fn hours(&mut self) -> Result<Hours, Error<E>> {
+        self.get_time()
+            .map(|date_time| Hours::H24(date_time.hour()))
+    }
+
+    fn time(&mut self) -> Result<NaiveTime, Error<E>> {
+        self.get_naive_datetime().map(|date_time| date_time.time())
+    }
+
+    /// 1 (Sunday) to 7 (Saturday), as counted by the WEEK register
+    fn weekday(&mut self) -> Result<u8, Error<E>> {
+        let registers = self.read_registers::<TIME_READ_SIZE>(REGISTER_SEC)?;
+        check_time_validity(&registers)?;
+        let week = registers[(REGISTER_WEEK - REGISTER_SEC) as usize];
+        if week.count_ones() != 1 || week > 0x40 {
+            return Err(Error::InvalidRegister);
+        }
+        Ok(week.trailing_zeros() as u8 + 1)
+    }
+
+    fn day(&mut self) -> Result<u8, Error<E>> {
+        self.get_time().map(|date_time| date_time.day())
+    }
+
+    fn month(&mut self) -> Result<u8, Error<E>> {
+        self.get_time().map(|date_time| date_time.month())
+    }
+
+    fn year(&mut self) -> Result<u16, Error<E>> {
+        self.get_time().map(|date_time| date_time.year())
+    }
+
+    fn date(&mut self) -> Result<NaiveDate, Error<E>> {
+        self.get_naive_datetime().map(|date_time| date_time.date())
+    }
+
+    fn set_seconds(&mut self, seconds: u8) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(t.year(), t.month(), t.day(), t.hour(), t.minute(), seconds)
+        })
+    }
+
+    fn set_minutes(&mut self, minutes: u8) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(t.year(), t.month(), t.day(), t.hour(), minutes, t.second())
+        })
+    }
+
+    /// 12-hour values are converted to 24-hour
+    fn set_hours(&mut self, hours: Hours) -> Result<(), Error<E>> {
+        let hour = match hours {
+            Hours::H24(hour) => hour,
+            Hours::AM(12) => 0,
+            Hours::PM(12) => 12,
+            Hours::AM(hour @ 1..=11) => hour,
+            Hours::PM(hour @ 1..=11) => hour + 12,
+            Hours::AM(_) | Hours::PM(_) => return Err(Error::InvalidArgument),
+        };
+        self.replace_time(|t| {
+            DateTime::new(t.year(), t.month(), t.day(), hour, t.minute(), t.second())
+        })
+    }
+
+    fn set_time(&mut self, time: &NaiveTime) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(
+                t.year(),
+                t.month(),
+                t.day(),
+                time.hour() as u8,
+                time.minute() as u8,
+                time.second() as u8,
+            )
+        })
+    }
+
+    /// Writes the WEEK register alone, 1 (Sunday) to 7 (Saturday).
+    /// Weekday alarms follow this register; it is recomputed from the date
+    /// the next time the date is set.
+    fn set_weekday(&mut self, weekday: u8) -> Result<(), Error<E>> {
+        if !(1..=7).contains(&weekday) {
+            return Err(Error::InvalidArgument);
+        }
+        self.write_register(REGISTER_WEEK, 1 << (weekday - 1))
+    }
+
+    fn set_day(&mut self, day: u8) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(t.year(), t.month(), day, t.hour(), t.minute(), t.second())
+        })
+    }
+
+    fn set_month(&mut self, month: u8) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(t.year(), month, t.day(), t.hour(), t.minute(), t.second())
+        })
+    }
+
+    /// Only 2000-2099, other years fail with `UnsupportedDate`
+    fn set_year(&mut self, year: u16) -> Result<(), Error<E>> {
+        self.replace_time(|t| {
+            DateTime::new(year, t.month(), t.day(), t.hour(), t.minute(), t.second())
+        })
+    }
+
+    fn set_date(&mut self, date: &NaiveDate) -> Result<(), Error<E>> {
+        let year = u16::try_from(date.year()).map_err(|_| Error::UnsupportedDate)?;
+        self.replace_time(|t| {
+            DateTime::new(
+                year,
+                date.month() as u8,
+                date.day() as u8,
+                t.hour(),
+                t.minute(),
+                t.second(),
+            )
+        })
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Reads the time, passes it through `replace` and writes the result back
+    fn replace_time<F>(&mut self, replace: F) -> Result<(), Error<E>>
+    where
+        F: FnOnce(DateTime) -> Option<DateTime>,
+    {
+        let date_time = self.get_time()?;
+        let date_time = replace(date_time).ok_or(Error::InvalidArgument)?;
+        // Inherent method, not `Rtcc::set_time`
+        self.set_time(date_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use crate::sim::testing::{date_time, running_at};
+
+    #[test]
+    fn set_hours_12_hour_conversion() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 30, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_hours(Hours::AM(12)).unwrap();
+        assert_eq!(rtc.hours(), Ok(Hours::H24(0)));
+        rtc.set_hours(Hours::PM(12)).unwrap();
+        assert_eq!(rtc.hours(), Ok(Hours::H24(12)));
+        rtc.set_hours(Hours::PM(11)).unwrap();
+        assert_eq!(rtc.hours(), Ok(Hours::H24(23)));
+        assert_eq!(rtc.set_hours(Hours::AM(13)), Err(Error::InvalidArgument));
+        assert_eq!(rtc.set_hours(Hours::PM(0)), Err(Error::InvalidArgument));
+        assert_eq!(rtc.get_time(), Ok(date_time(2024, 3, 1, 23, 30, 0)));
+    }
+
+    #[test]
+    fn weekday_follows_the_week_register() {
+        // 2024-03-01 is a Friday
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 30, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.weekday(), Ok(6));
+        assert_eq!(
+            1 << (rtc.weekday().unwrap() - 1),
+            sim.register(Register::WEEK)
+        );
+
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_weekday(1).unwrap();
+        assert_eq!(sim.register(Register::WEEK), 0x01);
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.weekday(), Ok(1));
+        assert_eq!(rtc.set_weekday(8), Err(Error::InvalidArgument));
+
+        // Setting the date recomputes it
+        rtc.set_day(3).unwrap();
+        assert_eq!(rtc.weekday(), Ok(1));
+        rtc.set_day(4).unwrap();
+        assert_eq!(rtc.weekday(), Ok(2));
+    }
+
+    #[test]
+    fn setters_replace_one_field() {
+        let mut sim = running_at(date_time(2024, 1, 31, 7, 30, 15));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_year(2025).unwrap();
+        rtc.set_minutes(45).unwrap();
+        assert_eq!(rtc.get_time(), Ok(date_time(2025, 1, 31, 7, 45, 15)));
+        assert_eq!(rtc.set_month(2), Err(Error::InvalidArgument));
+        assert_eq!(rtc.set_year(2100), Err(Error::UnsupportedDate));
+        assert_eq!(rtc.get_time(), Ok(date_time(2025, 1, 31, 7, 45, 15)));
+    }
+}
@@ -0,0 +1,610 @@
+//! Register-level model of the RX-8010SJ, to run code using `Rx8010sj` on a
+//! host. The simulated chip sits on its own `I2c` bus and only moves forward
+//! in time when `advance` is called.
+//!
+//! Modeled: register auto-increment, BCD calendar, alarm, countdown timer,
+//! update interrupt, flags, user RAM, STOP and VLF. Simplifications: STOP
+//! freezes everything (calendar, timer and update events), /IRQ stays low
+//! until the flag that caused it is cleared, and the FOPIN/TMPIN output pin
+//! routing is not modeled. Registers holding invalid BCD values are not
+//! counted.
+
+use core::time::Duration;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+use crate::calendar::days_in_month;
+use crate::registers::{
+    Register, BIT_ALARM_AE, BIT_REGISTER_CONTROL_AIE, BIT_REGISTER_CONTROL_STOP,
+    BIT_REGISTER_CONTROL_TIE, BIT_REGISTER_CONTROL_TSTP, BIT_REGISTER_CONTROL_UIE,
+    BIT_REGISTER_EXTENSION_TE, BIT_REGISTER_EXTENSION_USEL, BIT_REGISTER_EXTENSION_WADA,
+    BIT_REGISTER_FLAG_AF, BIT_REGISTER_FLAG_TF, BIT_REGISTER_FLAG_UF, BIT_REGISTER_FLAG_VLF,
+    MASK_REGISTER_FLAG, REGISTER_ALARM_HOUR, REGISTER_ALARM_MIN, REGISTER_ALARM_WEEK_DAY,
+    REGISTER_CONTROL, REGISTER_DAY, REGISTER_EXTENSION, REGISTER_FLAG, REGISTER_HOUR, REGISTER_MIN,
+    REGISTER_MONTH, REGISTER_SEC, REGISTER_TIMER_COUNTER_0, REGISTER_TIMER_COUNTER_1,
+    REGISTER_WEEK, REGISTER_YEAR,
+};
+use crate::{
+    bcd2bin, bin2bcd, decode_time, DateTime, FoutFrequency, TimerClock, CENTURY, DEFAULT_ADDRESS,
+};
+
+const REGISTER_COUNT: usize = (Register::LAST.address() - Register::FIRST.address()) as usize + 1;
+
+/// Resolution of the virtual clock, the fastest timer source
+const TICKS_PER_SECOND: u32 = 4096;
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Calendar units that rolled over while counting a second
+#[derive(Default)]
+struct Rollover {
+    minute: bool,
+    hour: bool,
+}
+
+/// Simulated RX-8010SJ; `Rx8010sj::new(Rx8010sjSim::new())` drives it like
+/// the real chip (pass `&mut sim` to keep access to it).
+#[derive(Debug, Clone)]
+pub struct Rx8010sjSim {
+    address: u8,
+    registers: [u8; REGISTER_COUNT],
+    /// Register address auto-incremented by reads and writes
+    pointer: u8,
+    /// Ticks elapsed in the current second
+    subsecond: u32,
+    /// Fraction of a tick carried between calls to `advance`, in ns * ticks
+    remainder: u128,
+    /// Current count of the timer, the counter registers hold the preset
+    timer_count: u16,
+}
+
+impl Default for Rx8010sjSim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rx8010sjSim {
+    /// Chip right after a cold power-on: VLF set, everything else cleared
+    /// and the calendar at 2000-01-01T00:00:00 (a Saturday)
+    pub fn new() -> Self {
+        let mut sim = Rx8010sjSim {
+            address: DEFAULT_ADDRESS,
+            registers: [0; REGISTER_COUNT],
+            pointer: 0,
+            subsecond: 0,
+            remainder: 0,
+            timer_count: 0,
+        };
+        sim.set_register(Register::WEEK, 1 << 6);
+        sim.set_register(Register::DAY, 0x01);
+        sim.set_register(Register::MONTH, 0x01);
+        sim.set_register(Register::FLAG, BIT_REGISTER_FLAG_VLF);
+        sim
+    }
+
+    /// Answers on `address` instead of the default one
+    pub fn with_address(self, address: u8) -> Self {
+        Rx8010sjSim { address, ..self }
+    }
+
+    /// Moves the virtual clock forward, raising every event that falls in
+    /// `duration`. Time does not pass while STOP is set.
+    pub fn advance(&mut self, duration: Duration) {
+        let scaled = self.remainder + duration.as_nanos() * TICKS_PER_SECOND as u128;
+        self.remainder = scaled % NANOS_PER_SECOND;
+        let mut ticks = scaled / NANOS_PER_SECOND;
+
+        if (self.load(REGISTER_CONTROL) & BIT_REGISTER_CONTROL_STOP) > 0 {
+            return;
+        }
+
+        while ticks > 0 {
+            let step = ticks.min((TICKS_PER_SECOND - self.subsecond) as u128) as u32;
+            self.count_fast_timer(step);
+            self.subsecond += step;
+            ticks -= step as u128;
+
+            if self.subsecond == TICKS_PER_SECOND {
+                self.subsecond = 0;
+                self.count_second();
+            }
+        }
+    }
+
+    /// Decoded calendar, `None` if the registers do not hold a valid date
+    pub fn time(&self) -> Option<DateTime> {
+        let start = (REGISTER_SEC - Register::FIRST.address()) as usize;
+        decode_time(&self.registers[start..start + 7])
+    }
+
+    /// Reads a register without side effects
+    pub fn register(&self, register: Register) -> u8 {
+        self.load(register.address())
+    }
+
+    /// Overwrites a register without side effects, e.g. to set up a corrupt
+    /// calendar
+    pub fn set_register(&mut self, register: Register, value: u8) {
+        self.registers[(register.address() - Register::FIRST.address()) as usize] = value;
+    }
+
+    /// Simulates a supply drop below the data retention voltage (sets VLF)
+    pub fn set_voltage_low(&mut self) {
+        self.raise_flags(BIT_REGISTER_FLAG_VLF);
+    }
+
+    /// True while /IRQ is driven low by a flag whose interrupt is enabled
+    pub fn irq_asserted(&self) -> bool {
+        let flag = self.load(REGISTER_FLAG);
+        let control = self.load(REGISTER_CONTROL);
+        [
+            (BIT_REGISTER_FLAG_AF, BIT_REGISTER_CONTROL_AIE),
+            (BIT_REGISTER_FLAG_TF, BIT_REGISTER_CONTROL_TIE),
+            (BIT_REGISTER_FLAG_UF, BIT_REGISTER_CONTROL_UIE),
+        ]
+        .iter()
+        .any(|(flag_bit, enable_bit)| (flag & flag_bit) > 0 && (control & enable_bit) > 0)
+    }
+
+    /// Frequency currently driven on FOUT
+    pub fn fout(&self) -> FoutFrequency {
+        FoutFrequency::from_bits(self.load(REGISTER_EXTENSION))
+    }
+
+    /// Unmapped addresses read as 0
+    fn load(&self, address: u8) -> u8 {
+        match address.checked_sub(Register::FIRST.address()) {
+            Some(index) if (index as usize) < REGISTER_COUNT => self.registers[index as usize],
+            _ => 0,
+        }
+    }
+
+    /// Register write as seen from the bus; unmapped addresses are ignored
+    fn store(&mut self, address: u8, value: u8) {
+        let Some(register) = Register::new(address) else {
+            return;
+        };
+
+        match address {
+            // Writing 0 clears a flag, writing 1 has no effect
+            REGISTER_FLAG => {
+                let flag = self.load(REGISTER_FLAG);
+                self.set_register(register, flag & value & MASK_REGISTER_FLAG);
+            }
+            REGISTER_EXTENSION => {
+                let extension = self.load(REGISTER_EXTENSION);
+                if (extension & BIT_REGISTER_EXTENSION_TE) == 0
+                    && (value & BIT_REGISTER_EXTENSION_TE) > 0
+                {
+                    self.timer_count = self.timer_preset();
+                }
+                self.set_register(register, value);
+            }
+            REGISTER_CONTROL => {
+                // The divider is held in reset while stopped, so that the
+                // first second after release is a whole one
+                if (value & BIT_REGISTER_CONTROL_STOP) > 0 {
+                    self.subsecond = 0;
+                }
+                self.set_register(register, value);
+            }
+            _ => self.set_register(register, value),
+        }
+    }
+
+    fn raise_flags(&mut self, flags: u8) {
+        let flag = self.load(REGISTER_FLAG);
+        self.set_register(Register::FLAG, flag | flags);
+    }
+
+    fn timer_preset(&self) -> u16 {
+        u16::from_le_bytes([
+            self.load(REGISTER_TIMER_COUNTER_0),
+            self.load(REGISTER_TIMER_COUNTER_1),
+        ])
+    }
+
+    /// Counts the 4096 Hz and 64 Hz timer sources over `ticks` ticks
+    fn count_fast_timer(&mut self, ticks: u32) {
+        let period = match TimerClock::from_bits(self.load(REGISTER_EXTENSION)) {
+            TimerClock::Hz4096 => 1,
+            TimerClock::Hz64 => TICKS_PER_SECOND / 64,
+            _ => return,
+        };
+        let edges = (self.subsecond + ticks) / period - self.subsecond / period;
+        self.count_timer(edges);
+    }
+
+    /// Counts down `edges` edges of the timer source, raising TF and
+    /// reloading the preset every time the count reaches 0
+    fn count_timer(&mut self, mut edges: u32) {
+        let running = (self.load(REGISTER_EXTENSION) & BIT_REGISTER_EXTENSION_TE) > 0
+            && (self.load(REGISTER_CONTROL) & BIT_REGISTER_CONTROL_TSTP) == 0;
+        if !running || self.timer_count == 0 {
+            return;
+        }
+
+        while edges >= self.timer_count as u32 {
+            edges -= self.timer_count as u32;
+            self.raise_flags(BIT_REGISTER_FLAG_TF);
+            self.timer_count = self.timer_preset();
+            if self.timer_count == 0 {
+                return;
+            }
+        }
+        self.timer_count -= edges as u16;
+    }
+
+    /// End of a second: calendar, update interrupt, alarm and slow timer
+    /// sources
+    fn count_second(&mut self) {
+        let rollover = self.count_calendar();
+        let extension = self.load(REGISTER_EXTENSION);
+
+        if (extension & BIT_REGISTER_EXTENSION_USEL) == 0 || rollover.minute {
+            self.raise_flags(BIT_REGISTER_FLAG_UF);
+        }
+        if rollover.minute && self.alarm_matches() {
+            self.raise_flags(BIT_REGISTER_FLAG_AF);
+        }
+        match TimerClock::from_bits(extension) {
+            TimerClock::Hz1 => self.count_timer(1),
+            TimerClock::PerMinute if rollover.minute => self.count_timer(1),
+            TimerClock::PerHour if rollover.hour => self.count_timer(1),
+            _ => {}
+        }
+    }
+
+    /// Adds one second to the calendar registers
+    fn count_calendar(&mut self) -> Rollover {
+        let mut rollover = Rollover::default();
+        let fields = [
+            REGISTER_SEC,
+            REGISTER_MIN,
+            REGISTER_HOUR,
+            REGISTER_DAY,
+            REGISTER_MONTH,
+            REGISTER_YEAR,
+        ]
+        .map(|register| bcd2bin(self.load(register)));
+        let [Some(second), Some(minute), Some(hour), Some(day), Some(month), Some(year)] = fields
+        else {
+            return rollover;
+        };
+
+        if second < 59 {
+            self.set_register(Register::SEC, bin2bcd(second + 1));
+            return rollover;
+        }
+        self.set_register(Register::SEC, 0);
+        rollover.minute = true;
+
+        if minute < 59 {
+            self.set_register(Register::MIN, bin2bcd(minute + 1));
+            return rollover;
+        }
+        self.set_register(Register::MIN, 0);
+        rollover.hour = true;
+
+        if hour < 23 {
+            self.set_register(Register::HOUR, bin2bcd(hour + 1));
+            return rollover;
+        }
+        self.set_register(Register::HOUR, 0);
+
+        // The one-hot weekday rotates on its own, independently of the date
+        let week = self.load(REGISTER_WEEK);
+        self.set_register(Register::WEEK, ((week << 1) | (week >> 6)) & 0x7F);
+
+        if day < days_in_month(CENTURY + year as u16, month) {
+            self.set_register(Register::DAY, bin2bcd(day + 1));
+            return rollover;
+        }
+        self.set_register(Register::DAY, 0x01);
+
+        if month < 12 {
+            self.set_register(Register::MONTH, bin2bcd(month + 1));
+            return rollover;
+        }
+        self.set_register(Register::MONTH, 0x01);
+        self.set_register(Register::YEAR, bin2bcd((year + 1) % 100));
+        rollover
+    }
+
+    /// Compares the alarm registers with the calendar, fields with AE set
+    /// always match
+    fn alarm_matches(&self) -> bool {
+        let field_matches = |alarm: u8, current: u8| (alarm & BIT_ALARM_AE) > 0 || alarm == current;
+        let alarm_day = self.load(REGISTER_ALARM_WEEK_DAY);
+        let day_matches = if (alarm_day & BIT_ALARM_AE) > 0 {
+            true
+        } else if (self.load(REGISTER_EXTENSION) & BIT_REGISTER_EXTENSION_WADA) > 0 {
+            alarm_day == self.load(REGISTER_DAY)
+        } else {
+            (alarm_day & self.load(REGISTER_WEEK)) > 0
+        };
+
+        field_matches(self.load(REGISTER_ALARM_MIN), self.load(REGISTER_MIN))
+            && field_matches(self.load(REGISTER_ALARM_HOUR), self.load(REGISTER_HOUR))
+            && day_matches
+    }
+}
+
+impl ErrorType for Rx8010sjSim {
+    type Error = ErrorKind;
+}
+
+/// Any address other than the chip's is not acknowledged
+impl I2c for Rx8010sjSim {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        // Adjacent writes are merged, only the first byte after a (repeated)
+        // start is the register address
+        let mut expect_pointer = true;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    for byte in bytes.iter() {
+                        if expect_pointer {
+                            self.pointer = *byte;
+                            expect_pointer = false;
+                        } else {
+                            self.store(self.pointer, *byte);
+                            self.pointer = self.pointer.wrapping_add(1);
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.load(self.pointer);
+                        self.pointer = self.pointer.wrapping_add(1);
+                    }
+                    expect_pointer = true;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Same bus as the blocking implementation, for `Rx8010sjAsync`; transfers
+/// complete right away
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Rx8010sjSim {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
+
+/// Helpers shared by the tests driving `Rx8010sj` through the simulator
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::Rx8010sjSim;
+    use crate::{DateTime, Rx8010sj};
+
+    pub(crate) struct NoDelay;
+
+    impl embedded_hal::delay::DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    pub(crate) fn date_time(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> DateTime {
+        DateTime::new(year, month, day, hour, minute, second).unwrap()
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::delay::DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// Polls `future` once, without any waker to call back
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_once<F: core::future::Future>(
+        future: core::pin::Pin<&mut F>,
+    ) -> core::task::Poll<F::Output> {
+        future.poll(&mut core::task::Context::from_waker(
+            core::task::Waker::noop(),
+        ))
+    }
+
+    /// Runs a future that never waits, like every bus access to the
+    /// simulator
+    #[cfg(feature = "async")]
+    pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        match poll_once(core::pin::pin!(future)) {
+            core::task::Poll::Ready(output) => output,
+            core::task::Poll::Pending => panic!("the future is waiting"),
+        }
+    }
+
+    /// Simulator after `init` and `set_time`
+    pub(crate) fn running_at(time: DateTime) -> Rx8010sjSim {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.init(&mut NoDelay).unwrap();
+        rtc.set_time(time).unwrap();
+        sim
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::date_time;
+    use super::*;
+
+    const ADDRESS: u8 = DEFAULT_ADDRESS;
+
+    /// Sets the calendar through the bus, bypassing the driver
+    fn sim_at(time_registers: [u8; 7]) -> Rx8010sjSim {
+        let mut sim = Rx8010sjSim::new();
+        let mut write = [0; 8];
+        write[0] = REGISTER_SEC;
+        write[1..].copy_from_slice(&time_registers);
+        sim.write(ADDRESS, &write).unwrap();
+        sim
+    }
+
+    #[test]
+    fn cold_power_on_state() {
+        let sim = Rx8010sjSim::new();
+        assert_eq!(sim.time(), Some(date_time(2000, 1, 1, 0, 0, 0)));
+        assert_eq!(sim.register(Register::FLAG), BIT_REGISTER_FLAG_VLF);
+        assert_eq!(sim.register(Register::CONTROL), 0x00);
+        assert!(!sim.irq_asserted());
+        assert_eq!(sim.fout(), FoutFrequency::Off);
+    }
+
+    #[test]
+    fn auto_increment_and_merged_writes() {
+        let mut sim = Rx8010sjSim::new();
+        // Only the first byte after a start is the register address
+        sim.transaction(
+            ADDRESS,
+            &mut [
+                Operation::Write(&[Register::RAM.address()]),
+                Operation::Write(&[1, 2]),
+            ],
+        )
+        .unwrap();
+        sim.write(ADDRESS, &[Register::RAM.address() + 2, 3])
+            .unwrap();
+
+        let mut read = [0; 4];
+        sim.write_read(ADDRESS, &[Register::RAM.address()], &mut read)
+            .unwrap();
+        assert_eq!(read, [1, 2, 3, 0]);
+        // Reads go on from where the last one stopped
+        let mut read = [0; 1];
+        sim.read(ADDRESS, &mut read).unwrap();
+        assert_eq!(read, [0]);
+        assert_eq!(sim.register(Register::ram(4).unwrap()), 0);
+    }
+
+    #[test]
+    fn unmapped_addresses() {
+        let mut sim = Rx8010sjSim::new();
+        sim.write(ADDRESS, &[0x40, 0xFF]).unwrap();
+        let mut read = [0xFF; 2];
+        sim.write_read(ADDRESS, &[0x0F], &mut read).unwrap();
+        assert_eq!(read, [0x00, 0x00]);
+    }
+
+    #[test]
+    fn flags_are_only_cleared_by_writes() {
+        let mut sim = Rx8010sjSim::new();
+        sim.write(ADDRESS, &[REGISTER_FLAG, 0xFF]).unwrap();
+        assert_eq!(sim.register(Register::FLAG), BIT_REGISTER_FLAG_VLF);
+        sim.write(ADDRESS, &[REGISTER_FLAG, 0x00]).unwrap();
+        assert_eq!(sim.register(Register::FLAG), 0x00);
+        sim.set_voltage_low();
+        assert_eq!(sim.register(Register::FLAG), BIT_REGISTER_FLAG_VLF);
+    }
+
+    #[test]
+    fn calendar_rollover() {
+        // 2099-12-31T23:59:59, a Thursday
+        let mut sim = sim_at([0x59, 0x59, 0x23, 0x10, 0x31, 0x12, 0x99]);
+        sim.advance(Duration::from_millis(999));
+        assert_eq!(sim.time(), Some(date_time(2099, 12, 31, 23, 59, 59)));
+        sim.advance(Duration::from_millis(1));
+        assert_eq!(sim.time(), Some(date_time(2000, 1, 1, 0, 0, 0)));
+        assert_eq!(sim.register(Register::WEEK), 0x20);
+
+        // 2024-02-28T23:59:59, leap year
+        let mut sim = sim_at([0x59, 0x59, 0x23, 0x10, 0x28, 0x02, 0x24]);
+        sim.advance(Duration::from_secs(1));
+        assert_eq!(sim.time(), Some(date_time(2024, 2, 29, 0, 0, 0)));
+        sim.advance(Duration::from_secs(86_400));
+        assert_eq!(sim.time(), Some(date_time(2024, 3, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn invalid_calendar_is_not_counted() {
+        let mut sim = sim_at([0x5A, 0x59, 0x23, 0x10, 0x31, 0x12, 0x99]);
+        sim.advance(Duration::from_secs(3));
+        assert_eq!(sim.register(Register::SEC), 0x5A);
+    }
+
+    #[test]
+    fn stop_freezes_and_resets_the_divider() {
+        let mut sim = sim_at([0x00, 0x00, 0x12, 0x01, 0x01, 0x06, 0x24]);
+        sim.advance(Duration::from_millis(700));
+        sim.write(ADDRESS, &[REGISTER_CONTROL, BIT_REGISTER_CONTROL_STOP])
+            .unwrap();
+        sim.advance(Duration::from_secs(10));
+        assert_eq!(sim.time(), Some(date_time(2024, 6, 1, 12, 0, 0)));
+
+        // The first second after release is a whole one
+        sim.write(ADDRESS, &[REGISTER_CONTROL, 0x00]).unwrap();
+        sim.advance(Duration::from_millis(999));
+        assert_eq!(sim.time(), Some(date_time(2024, 6, 1, 12, 0, 0)));
+        sim.advance(Duration::from_millis(1));
+        assert_eq!(sim.time(), Some(date_time(2024, 6, 1, 12, 0, 1)));
+    }
+
+    #[test]
+    fn fast_timer_reloads_and_drives_irq() {
+        let mut sim = Rx8010sjSim::new();
+        // 4096 Hz, 4096 ticks: once per second
+        sim.write(ADDRESS, &[REGISTER_TIMER_COUNTER_0, 0x00, 0x10])
+            .unwrap();
+        sim.write(ADDRESS, &[REGISTER_EXTENSION, BIT_REGISTER_EXTENSION_TE])
+            .unwrap();
+        sim.write(ADDRESS, &[REGISTER_FLAG, 0x00]).unwrap();
+
+        sim.advance(Duration::from_millis(999));
+        assert_eq!(sim.register(Register::FLAG) & BIT_REGISTER_FLAG_TF, 0);
+        sim.advance(Duration::from_millis(1));
+        assert_eq!(
+            sim.register(Register::FLAG) & BIT_REGISTER_FLAG_TF,
+            BIT_REGISTER_FLAG_TF
+        );
+        // TIE is off
+        assert!(!sim.irq_asserted());
+        sim.write(ADDRESS, &[REGISTER_CONTROL, BIT_REGISTER_CONTROL_TIE])
+            .unwrap();
+        assert!(sim.irq_asserted());
+
+        sim.write(ADDRESS, &[REGISTER_FLAG, 0x00]).unwrap();
+        assert!(!sim.irq_asserted());
+        sim.advance(Duration::from_secs(1));
+        assert!(sim.irq_asserted());
+    }
+
+    #[test]
+    fn fout_follows_fsel() {
+        let mut sim = Rx8010sjSim::new();
+        sim.write(ADDRESS, &[REGISTER_EXTENSION, FoutFrequency::Hz1024.bits()])
+            .unwrap();
+        assert_eq!(sim.fout(), FoutFrequency::Hz1024);
+    }
+
+    #[test]
+    fn address_is_acknowledged_only_when_it_matches() {
+        let mut sim = Rx8010sjSim::default();
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        assert_eq!(sim.write(0x10, &[REGISTER_SEC]), Err(nack));
+
+        let mut sim = sim.with_address(0x10);
+        assert_eq!(sim.write(0x10, &[REGISTER_SEC]), Ok(()));
+        assert_eq!(sim.write(ADDRESS, &[REGISTER_SEC]), Err(nack));
+    }
+}
@@ -0,0 +1,274 @@
+use core::fmt;
+
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{
+    BitfieldRegister, Control, Extension, Flag, IrqControl, Register, RESERVED_REGISTERS,
+};
+use crate::{
+    decode_time, Alarm, AlarmDay, DateTime, Error, Rx8010sj, TimerClock, TimerConfig, RAM_SIZE,
+};
+
+const SNAPSHOT_SIZE: usize = (Register::LAST.address() - Register::FIRST.address()) as usize + 1;
+
+/// Copy of every documented register (0x10-0x32), taken in a single burst read.
+/// The `Display` implementation prints a decoded, multi-line dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterSnapshot {
+    registers: [u8; SNAPSHOT_SIZE],
+}
+
+impl RegisterSnapshot {
+    pub fn register(&self, register: Register) -> u8 {
+        self.registers[(register.address() - Register::FIRST.address()) as usize]
+    }
+
+    /// All the registers, starting from SEC (0x10)
+    pub fn raw(&self) -> &[u8; SNAPSHOT_SIZE] {
+        &self.registers
+    }
+
+    /// Decoded calendar, `None` if the registers do not hold a valid date
+    pub fn time(&self) -> Option<DateTime> {
+        decode_time(&self.registers[..7])
+    }
+
+    /// Decoded alarm, `None` if the alarm registers hold invalid values
+    pub fn alarm(&self) -> Option<Alarm> {
+        Alarm::from_registers(
+            [
+                self.register(Register::ALARM_MIN),
+                self.register(Register::ALARM_HOUR),
+                self.register(Register::ALARM_WEEK_DAY),
+            ],
+            self.register(Register::EXTENSION),
+        )
+    }
+
+    pub fn timer(&self) -> TimerConfig {
+        TimerConfig::from_registers(
+            [
+                self.register(Register::TIMER_COUNTER_0),
+                self.register(Register::TIMER_COUNTER_1),
+            ],
+            self.register(Register::EXTENSION),
+        )
+    }
+
+    pub fn extension(&self) -> Extension {
+        self.bitfield()
+    }
+
+    pub fn flag(&self) -> Flag {
+        self.bitfield()
+    }
+
+    pub fn control(&self) -> Control {
+        self.bitfield()
+    }
+
+    pub fn irq_control(&self) -> IrqControl {
+        self.bitfield()
+    }
+
+    pub fn ram(&self) -> [u8; RAM_SIZE] {
+        let start = (Register::RAM.address() - Register::FIRST.address()) as usize;
+        let mut ram = [0; RAM_SIZE];
+        ram.copy_from_slice(&self.registers[start..start + RAM_SIZE]);
+        ram
+    }
+
+    /// True if all the reserved registers hold their required values
+    pub fn reserved_ok(&self) -> bool {
+        RESERVED_REGISTERS.iter().all(|(reg, value)| {
+            self.registers[(reg - Register::FIRST.address()) as usize] == *value
+        })
+    }
+
+    fn bitfield<R: BitfieldRegister>(&self) -> R {
+        R::from_bits(self.register(R::REGISTER))
+    }
+}
+
+impl fmt::Display for RegisterSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bit = |value: bool| if value { 1 } else { 0 };
+
+        // BCD registers print as decimal when formatted in hex
+        writeln!(
+            f,
+            "time:      20{:02x}-{:02x}-{:02x} {:02x}:{:02x}:{:02x} week=0x{:02x} ({})",
+            self.register(Register::YEAR),
+            self.register(Register::MONTH),
+            self.register(Register::DAY),
+            self.register(Register::HOUR),
+            self.register(Register::MIN),
+            self.register(Register::SEC),
+            self.register(Register::WEEK),
+            if self.time().is_some() {
+                "valid"
+            } else {
+                "INVALID"
+            },
+        )?;
+
+        write!(f, "alarm:     ")?;
+        match self.alarm() {
+            Some(alarm) => {
+                match alarm.minute {
+                    Some(minute) => write!(f, "min={} ", minute)?,
+                    None => write!(f, "min=* ")?,
+                }
+                match alarm.hour {
+                    Some(hour) => write!(f, "hour={} ", hour)?,
+                    None => write!(f, "hour=* ")?,
+                }
+                match alarm.day {
+                    AlarmDay::Any => writeln!(f, "day=*")?,
+                    AlarmDay::DayOfMonth(day) => writeln!(f, "day={}", day)?,
+                    AlarmDay::Weekdays(weekdays) => {
+                        writeln!(f, "weekdays=0x{:02x}", weekdays.bits())?
+                    }
+                }
+            }
+            None => writeln!(
+                f,
+                "INVALID (0x{:02x} 0x{:02x} 0x{:02x})",
+                self.register(Register::ALARM_MIN),
+                self.register(Register::ALARM_HOUR),
+                self.register(Register::ALARM_WEEK_DAY),
+            )?,
+        }
+
+        let timer = self.timer();
+        writeln!(
+            f,
+            "timer:     count={} clock={}",
+            timer.count,
+            match timer.clock {
+                TimerClock::Hz4096 => "4096Hz",
+                TimerClock::Hz64 => "64Hz",
+                TimerClock::Hz1 => "1Hz",
+                TimerClock::PerMinute => "1/60Hz",
+                TimerClock::PerHour => "1/3600Hz",
+            },
+        )?;
+
+        let extension = self.extension();
+        writeln!(
+            f,
+            "extension: 0x{:02x} FSEL={:?} USEL={:?} TE={} WADA={} TSEL={:?}",
+            extension.bits(),
+            extension.fout_frequency(),
+            extension.update_interval(),
+            bit(extension.timer_enabled()),
+            bit(extension.wada()),
+            extension.timer_clock(),
+        )?;
+
+        let flag = self.flag();
+        writeln!(
+            f,
+            "flag:      0x{:02x} VLF={} AF={} TF={} UF={}",
+            flag.bits(),
+            bit(flag.vlf()),
+            bit(flag.af()),
+            bit(flag.tf()),
+            bit(flag.uf()),
+        )?;
+
+        let control = self.control();
+        writeln!(
+            f,
+            "control:   0x{:02x} TEST={} STOP={} UIE={} TIE={} AIE={} TSTP={} CSEL={:?}",
+            control.bits(),
+            bit(control.test()),
+            bit(control.stop()),
+            bit(control.uie()),
+            bit(control.tie()),
+            bit(control.aie()),
+            bit(control.tstp()),
+            control.compensation_interval(),
+        )?;
+
+        let irq_control = self.irq_control();
+        writeln!(
+            f,
+            "irq:       0x{:02x} FOPIN1={} FOPIN0={} TMPIN={}",
+            irq_control.bits(),
+            bit(irq_control.fopin1()),
+            bit(irq_control.fopin0()),
+            bit(irq_control.tmpin()),
+        )?;
+
+        write!(f, "reserved: ")?;
+        for (reg, value) in RESERVED_REGISTERS {
+            let actual = self.registers[(reg - Register::FIRST.address()) as usize];
+            if actual == value {
+                write!(f, " 0x{:02x}=0x{:02x}", reg, actual)?;
+            } else {
+                write!(f, " 0x{:02x}=0x{:02x}(!=0x{:02x})", reg, actual, value)?;
+            }
+        }
+        writeln!(f, " ({})", if self.reserved_ok() { "ok" } else { "BAD" })?;
+
+        write!(f, "ram:      ")?;
+        for byte in self.ram() {
+            write!(f, " {:02x}", byte)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Burst-reads every documented register, for diagnostics
+    pub fn snapshot(&mut self) -> Result<RegisterSnapshot, Error<E>> {
+        let registers = self.read_registers::<SNAPSHOT_SIZE>(Register::FIRST.address())?;
+        Ok(RegisterSnapshot { registers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    #[test]
+    fn reserved_registers_after_init() {
+        let mut sim = running_at(date_time(2024, 2, 29, 12, 34, 56));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let snapshot = rtc.snapshot().unwrap();
+        assert!(snapshot.reserved_ok());
+        assert_eq!(snapshot.time(), Some(date_time(2024, 2, 29, 12, 34, 56)));
+        assert_eq!(snapshot.register(Register::RESERVED_17), 0xD8);
+
+        rtc.write_raw(Register::RESERVED_17, 0).unwrap();
+        let snapshot = rtc.snapshot().unwrap();
+        assert!(!snapshot.reserved_ok());
+        assert_eq!(snapshot.raw()[0x07], 0);
+    }
+
+    #[test]
+    fn display_dump() {
+        let mut sim = running_at(date_time(2024, 2, 29, 12, 34, 56));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.write_ram(0, &[0xAB]).unwrap();
+        let dump = rtc.snapshot().unwrap().to_string();
+        assert!(dump.starts_with("time:      2024-02-29 12:34:56 week=0x10 (valid)\n"));
+        assert!(dump.contains("flag:      0x00 VLF=0"));
+        assert!(dump.contains(" (ok)\n"));
+        assert!(dump.ends_with("ram:       ab 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n"));
+
+        rtc.write_raw(Register::RESERVED_17, 0).unwrap();
+        let dump = rtc.snapshot().unwrap().to_string();
+        assert!(dump.contains(" 0x17=0x00(!=0xd8)"));
+        assert!(dump.contains(" (BAD)\n"));
+    }
+}
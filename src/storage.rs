@@ -0,0 +1,89 @@
+use embedded_hal::i2c::I2c;
+use embedded_storage::{ReadStorage, Storage};
+
+use crate::{Error, Rx8010sj, RAM_SIZE};
+
+/// `embedded_storage` view of the 16 bytes of user RAM (registers 0x20-0x2F).
+/// Offsets are relative to the start of the RAM; accesses past its end fail
+/// with `Error::OutOfBounds`.
+pub struct RamStorage<'a, I2C> {
+    rtc: &'a mut Rx8010sj<I2C>,
+}
+
+impl<'a, I2C> RamStorage<'a, I2C> {
+    pub fn new(rtc: &'a mut Rx8010sj<I2C>) -> Self {
+        RamStorage { rtc }
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Borrows the user RAM as an `embedded_storage` device
+    pub fn ram_storage(&mut self) -> RamStorage<'_, I2C> {
+        RamStorage::new(self)
+    }
+}
+
+impl<I2C, E> ReadStorage for RamStorage<'_, I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    type Error = Error<E>;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let offset = usize::try_from(offset).map_err(|_| Error::OutOfBounds)?;
+        self.rtc.read_ram(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        RAM_SIZE
+    }
+}
+
+impl<I2C, E> Storage for RamStorage<'_, I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        let offset = usize::try_from(offset).map_err(|_| Error::OutOfBounds)?;
+        self.rtc.write_ram(offset, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registers::Register;
+    use crate::sim::Rx8010sjSim;
+
+    #[test]
+    fn capacity() {
+        let mut rtc = Rx8010sj::new(Rx8010sjSim::new());
+        assert_eq!(rtc.ram_storage().capacity(), 16);
+    }
+
+    #[test]
+    fn read_write_round_trip() {
+        let mut sim = Rx8010sjSim::new();
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let mut storage = rtc.ram_storage();
+        storage.write(4, &[1, 2, 3]).unwrap();
+        let mut bytes = [0; 5];
+        storage.read(3, &mut bytes).unwrap();
+        assert_eq!(bytes, [0, 1, 2, 3, 0]);
+        assert_eq!(sim.register(Register::RAM), 0);
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let mut rtc = Rx8010sj::new(Rx8010sjSim::new());
+        let mut storage = rtc.ram_storage();
+        assert_eq!(storage.write(15, &[1, 2]), Err(Error::OutOfBounds));
+        assert_eq!(storage.read(16, &mut [0]), Err(Error::OutOfBounds));
+        assert_eq!(storage.write(u32::MAX, &[1]), Err(Error::OutOfBounds));
+        assert_eq!(storage.read(u32::MAX, &mut [0]), Err(Error::OutOfBounds));
+        assert_eq!(storage.write(15, &[1]), Ok(()));
+    }
+}
@@ -0,0 +1,137 @@
+use embedded_hal::i2c::I2c;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::{DateTime, DateTimeRangeError, Error, Rx8010sj};
+
+/// Fails for years past 9999, which `time` only supports with its
+/// `large-dates` feature
+impl TryFrom<DateTime> for PrimitiveDateTime {
+    type Error = DateTimeRangeError;
+
+    fn try_from(date_time: DateTime) -> Result<Self, DateTimeRangeError> {
+        let month = Month::try_from(date_time.month()).map_err(|_| DateTimeRangeError)?;
+        let date = Date::from_calendar_date(date_time.year() as i32, month, date_time.day())
+            .map_err(|_| DateTimeRangeError)?;
+        let time = Time::from_hms(date_time.hour(), date_time.minute(), date_time.second())
+            .map_err(|_| DateTimeRangeError)?;
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+}
+
+/// Fails for years that do not fit in a `u16` (including negative years);
+/// sub-second precision is dropped.
+impl TryFrom<PrimitiveDateTime> for DateTime {
+    type Error = DateTimeRangeError;
+
+    fn try_from(date_time: PrimitiveDateTime) -> Result<Self, DateTimeRangeError> {
+        let year = u16::try_from(date_time.year()).map_err(|_| DateTimeRangeError)?;
+        DateTime::new(
+            year,
+            date_time.month().into(),
+            date_time.day(),
+            date_time.hour(),
+            date_time.minute(),
+            date_time.second(),
+        )
+        .ok_or(DateTimeRangeError)
+    }
+}
+
+/// Converted to UTC first
+impl TryFrom<OffsetDateTime> for DateTime {
+    type Error = DateTimeRangeError;
+
+    fn try_from(date_time: OffsetDateTime) -> Result<Self, DateTimeRangeError> {
+        let utc = date_time
+            .checked_to_offset(UtcOffset::UTC)
+            .ok_or(DateTimeRangeError)?;
+        DateTime::try_from(PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// `get_time` as a `time::PrimitiveDateTime`
+    pub fn get_primitive_datetime(&mut self) -> Result<PrimitiveDateTime, Error<E>> {
+        // The RTC only holds 2000-2099, which `time` always supports
+        let date_time = self.get_time()?;
+        PrimitiveDateTime::try_from(date_time).map_err(|_| Error::InvalidRegister)
+    }
+
+    /// `set_time` from a `time::PrimitiveDateTime`; dates outside of
+    /// 2000-2099 fail with `Error::UnsupportedDate`
+    pub fn set_primitive_datetime(&mut self, date_time: PrimitiveDateTime) -> Result<(), Error<E>> {
+        let date_time = DateTime::try_from(date_time).map_err(|_| Error::UnsupportedDate)?;
+        self.set_time(date_time)
+    }
+
+    /// `get_time` as a `time::OffsetDateTime`, assuming the RTC keeps UTC
+    pub fn get_offset_datetime(&mut self) -> Result<OffsetDateTime, Error<E>> {
+        self.get_primitive_datetime()
+            .map(PrimitiveDateTime::assume_utc)
+    }
+
+    /// Sets the RTC to the UTC equivalent of `date_time`; dates outside of
+    /// 2000-2099 (in UTC) fail with `Error::UnsupportedDate`
+    pub fn set_offset_datetime(&mut self, date_time: OffsetDateTime) -> Result<(), Error<E>> {
+        let date_time = DateTime::try_from(date_time).map_err(|_| Error::UnsupportedDate)?;
+        self.set_time(date_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    fn primitive(year: i32, month: Month, day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(
+            Date::from_calendar_date(year, month, day).unwrap(),
+            Time::from_hms(hour, minute, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn primitive_round_trip() {
+        let date_time = date_time(2024, 2, 29, 12, 34, 0);
+        let primitive_date_time = PrimitiveDateTime::try_from(date_time).unwrap();
+        assert_eq!(
+            primitive_date_time,
+            primitive(2024, Month::February, 29, 12, 34)
+        );
+        assert_eq!(DateTime::try_from(primitive_date_time), Ok(date_time));
+        assert_eq!(
+            DateTime::try_from(primitive(-1, Month::January, 1, 0, 0)),
+            Err(DateTimeRangeError)
+        );
+    }
+
+    #[test]
+    fn set_offset_datetime_converts_to_utc() {
+        let mut sim = running_at(date_time(2024, 1, 1, 0, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let offset = UtcOffset::from_hms(-2, 0, 0).unwrap();
+
+        let local = primitive(2024, Month::June, 30, 23, 0).assume_offset(offset);
+        rtc.set_offset_datetime(local).unwrap();
+        assert_eq!(rtc.get_time(), Ok(date_time(2024, 7, 1, 1, 0, 0)));
+        assert_eq!(
+            rtc.get_offset_datetime(),
+            Ok(primitive(2024, Month::July, 1, 1, 0).assume_utc())
+        );
+
+        // Still 2099 locally, but already 2100 in UTC
+        let local = primitive(2099, Month::December, 31, 23, 0).assume_offset(offset);
+        assert_eq!(rtc.set_offset_datetime(local), Err(Error::UnsupportedDate));
+        assert_eq!(
+            rtc.set_primitive_datetime(primitive(1999, Month::December, 31, 23, 0)),
+            Err(Error::UnsupportedDate)
+        );
+        assert_eq!(
+            rtc.get_primitive_datetime(),
+            Ok(primitive(2024, Month::July, 1, 1, 0))
+        );
+    }
+}
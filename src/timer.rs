@@ -0,0 +1,298 @@
+use core::time::Duration;
+
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{
+    BIT_REGISTER_CONTROL_TIE, BIT_REGISTER_CONTROL_TSTP, BIT_REGISTER_EXTENSION_TE,
+    BIT_REGISTER_FLAG_TF, MASK_REGISTER_EXTENSION_TSEL, REGISTER_CONTROL, REGISTER_EXTENSION,
+    REGISTER_FLAG, REGISTER_TIMER_COUNTER_0,
+};
+use crate::{Error, Rx8010sj};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// `(register, mask, value)` read-modify-write steps shared by both drivers
+pub(crate) const STOP_TIMER: [(u8, u8, u8); 1] =
+    [(REGISTER_EXTENSION, BIT_REGISTER_EXTENSION_TE, 0)];
+pub(crate) const START_TIMER: [(u8, u8, u8); 2] = [
+    (REGISTER_CONTROL, BIT_REGISTER_CONTROL_TSTP, 0),
+    (
+        REGISTER_EXTENSION,
+        BIT_REGISTER_EXTENSION_TE,
+        BIT_REGISTER_EXTENSION_TE,
+    ),
+];
+/// TE 1 -> 0 -> 1 reloads the preset count
+pub(crate) const RESTART_TIMER: [(u8, u8, u8); 3] = [STOP_TIMER[0], START_TIMER[0], START_TIMER[1]];
+
+/// Source clock of the countdown timer (TSEL bits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerClock {
+    Hz4096,
+    Hz64,
+    Hz1,
+    /// Once per minute
+    PerMinute,
+    /// Once per hour
+    PerHour,
+}
+
+impl TimerClock {
+    /// From the fastest to the slowest
+    const ALL: [TimerClock; 5] = [
+        TimerClock::Hz4096,
+        TimerClock::Hz64,
+        TimerClock::Hz1,
+        TimerClock::PerMinute,
+        TimerClock::PerHour,
+    ];
+
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            TimerClock::Hz4096 => 0b000,
+            TimerClock::Hz64 => 0b001,
+            TimerClock::Hz1 => 0b010,
+            TimerClock::PerMinute => 0b011,
+            TimerClock::PerHour => 0b100,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        match bits & MASK_REGISTER_EXTENSION_TSEL {
+            0b000 => TimerClock::Hz4096,
+            0b001 => TimerClock::Hz64,
+            0b010 => TimerClock::Hz1,
+            0b011 => TimerClock::PerMinute,
+            _ => TimerClock::PerHour,
+        }
+    }
+
+    /// Frequency as a (numerator, denominator) pair, in Hz
+    fn frequency(self) -> (u128, u128) {
+        match self {
+            TimerClock::Hz4096 => (4096, 1),
+            TimerClock::Hz64 => (64, 1),
+            TimerClock::Hz1 => (1, 1),
+            TimerClock::PerMinute => (1, 60),
+            TimerClock::PerHour => (1, 3600),
+        }
+    }
+}
+
+/// Countdown timer setting: the timer fires every `count` ticks of `clock`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerConfig {
+    pub clock: TimerClock,
+    pub count: u16,
+}
+
+impl TimerConfig {
+    /// Picks the finest source clock whose counter can hold the requested
+    /// duration (rounded to the nearest tick).
+    /// `None` if the duration is zero or longer than 65535 hours.
+    pub fn from_duration(duration: Duration) -> Option<TimerConfig> {
+        let nanos = duration.as_nanos();
+        if nanos == 0 {
+            return None;
+        }
+
+        TimerClock::ALL.into_iter().find_map(|clock| {
+            let (num, den) = clock.frequency();
+            let scale = NANOS_PER_SEC * den;
+            let ticks = ((nanos * num + scale / 2) / scale).max(1);
+            u16::try_from(ticks)
+                .ok()
+                .map(|count| TimerConfig { clock, count })
+        })
+    }
+
+    /// Encodes the preset count (TIMER COUNTER 0/1) and the TSEL bits of the
+    /// Extension register, `None` if the count is 0.
+    pub(crate) fn to_registers(self) -> Option<([u8; 2], u8)> {
+        if self.count == 0 {
+            None
+        } else {
+            Some((self.count.to_le_bytes(), self.clock.bits()))
+        }
+    }
+
+    pub(crate) fn from_registers(counter: [u8; 2], extension_register: u8) -> TimerConfig {
+        TimerConfig {
+            clock: TimerClock::from_bits(extension_register),
+            count: u16::from_le_bytes(counter),
+        }
+    }
+
+    /// Period of the timer
+    pub fn duration(&self) -> Duration {
+        let (num, den) = self.clock.frequency();
+        let nanos = self.count as u128 * den * NANOS_PER_SEC / num;
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Stops the timer and loads a new source clock and count; a count of 0
+    /// is rejected. Use `start_timer` to run it.
+    pub fn set_timer(&mut self, config: TimerConfig) -> Result<(), Error<E>> {
+        let (counter, tsel) = config.to_registers().ok_or(Error::InvalidArgument)?;
+        // The datasheet requires TE = 0 while the timer is being configured
+        self.modify_registers(&STOP_TIMER)?;
+        self.write_registers(REGISTER_TIMER_COUNTER_0, &counter)?;
+        self.modify_register(REGISTER_EXTENSION, MASK_REGISTER_EXTENSION_TSEL, tsel)
+    }
+
+    /// Like `set_timer`, with the setting picked by `TimerConfig::from_duration`.
+    pub fn set_timer_duration(&mut self, duration: Duration) -> Result<(), Error<E>> {
+        let config = TimerConfig::from_duration(duration).ok_or(Error::InvalidArgument)?;
+        self.set_timer(config)
+    }
+
+    /// Reads back the source clock and the preset count
+    pub fn get_timer(&mut self) -> Result<TimerConfig, Error<E>> {
+        let counter = self.read_registers::<2>(REGISTER_TIMER_COUNTER_0)?;
+        let extension_register = self.read_register(REGISTER_EXTENSION)?;
+        Ok(TimerConfig::from_registers(counter, extension_register))
+    }
+
+    /// Starts the countdown (TE = 1, TSTP = 0)
+    pub fn start_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&START_TIMER)
+    }
+
+    /// Stops the countdown (TE = 0)
+    pub fn stop_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&STOP_TIMER)
+    }
+
+    /// Reloads the preset count and starts counting down again
+    pub fn restart_timer(&mut self) -> Result<(), Error<E>> {
+        self.modify_registers(&RESTART_TIMER)
+    }
+
+    /// Enables the timer interrupt (TIE): /IRQ goes low when the count expires.
+    pub fn enable_timer_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_TIE,
+            BIT_REGISTER_CONTROL_TIE,
+        )
+    }
+
+    pub fn disable_timer_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(REGISTER_CONTROL, BIT_REGISTER_CONTROL_TIE, 0)
+    }
+
+    /// Reads the timer flag (TF)
+    pub fn is_timer_expired(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG)?;
+        Ok((flag_register & BIT_REGISTER_FLAG_TF) > 0)
+    }
+
+    /// Clears the timer flag
+    pub fn clear_timer_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_TF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    fn config(clock: TimerClock, count: u16) -> Option<TimerConfig> {
+        Some(TimerConfig { clock, count })
+    }
+
+    #[test]
+    fn from_duration_picks_finest_clock() {
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_millis(500)),
+            config(TimerClock::Hz4096, 2048)
+        );
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_secs(16)),
+            config(TimerClock::Hz64, 1024)
+        );
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_secs(3600)),
+            config(TimerClock::Hz1, 3600)
+        );
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_secs(65_536)),
+            config(TimerClock::PerMinute, 1092)
+        );
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_secs(65_535 * 3600)),
+            config(TimerClock::PerHour, 65_535)
+        );
+    }
+
+    #[test]
+    fn from_duration_rounding() {
+        // 4.096 ticks
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_millis(1)),
+            config(TimerClock::Hz4096, 4)
+        );
+        // Never rounds down to 0 ticks
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_nanos(100)),
+            config(TimerClock::Hz4096, 1)
+        );
+    }
+
+    #[test]
+    fn from_duration_out_of_range() {
+        assert_eq!(TimerConfig::from_duration(Duration::ZERO), None);
+        assert_eq!(
+            TimerConfig::from_duration(Duration::from_secs(65_536 * 3600)),
+            None
+        );
+    }
+
+    #[test]
+    fn duration_round_trip() {
+        let config = TimerConfig {
+            clock: TimerClock::Hz64,
+            count: 32,
+        };
+        assert_eq!(config.duration(), Duration::from_millis(500));
+        // The same period is picked up with the finer clock
+        assert_eq!(
+            TimerConfig::from_duration(config.duration()),
+            Some(TimerConfig {
+                clock: TimerClock::Hz4096,
+                count: 2048,
+            })
+        );
+    }
+
+    #[test]
+    fn timer_raises_flag_and_irq() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        let config = TimerConfig {
+            clock: TimerClock::Hz64,
+            count: 32,
+        };
+        rtc.set_timer(config).unwrap();
+        rtc.enable_timer_interrupt().unwrap();
+        rtc.start_timer().unwrap();
+
+        sim.advance(Duration::from_millis(499));
+        assert!(!sim.irq_asserted());
+        sim.advance(Duration::from_millis(2));
+        assert!(sim.irq_asserted());
+
+        // Auto-reload: fires again after another period
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.is_timer_expired(), Ok(true));
+        rtc.clear_timer_flag().unwrap();
+        sim.advance(Duration::from_millis(500));
+        assert_eq!(Rx8010sj::new(&mut sim).is_timer_expired(), Ok(true));
+    }
+}
@@ -0,0 +1,164 @@
+use embedded_hal::i2c::I2c;
+
+use crate::calendar::days_in_month;
+use crate::{bin2bcd, DateTime, Error, Rx8010sj, CENTURY};
+
+/// 2000-01-01T00:00:00Z
+const UNIX_2000: u32 = 946_684_800;
+/// 2099-12-31T23:59:59Z
+const UNIX_2099_END: u32 = 4_102_444_799;
+
+const SECONDS_PER_DAY: u32 = 86_400;
+/// 2000-01-01 was a Saturday
+const WEEKDAY_2000: u32 = 6;
+
+/// Every year divisible by 4 is a leap year in 2000-2099, 2000 included
+fn days_before_year(year: u32) -> u32 {
+    365 * year + year.div_ceil(4)
+}
+
+fn days_before_month(year: u32, month: u8) -> u32 {
+    (1..month)
+        .map(|m| days_in_month(CENTURY + year as u16, m) as u32)
+        .sum()
+}
+
+/// Seconds since the Unix epoch, `None` outside of 2000-2099
+fn unix_from_date_time(date_time: &DateTime) -> Option<u32> {
+    let year = date_time
+        .year()
+        .checked_sub(CENTURY)
+        .filter(|year| *year < 100)? as u32;
+    let days = days_before_year(year)
+        + days_before_month(year, date_time.month())
+        + date_time.day() as u32
+        - 1;
+    Some(
+        UNIX_2000
+            + days * SECONDS_PER_DAY
+            + date_time.hour() as u32 * 3600
+            + date_time.minute() as u32 * 60
+            + date_time.second() as u32,
+    )
+}
+
+/// SEC..YEAR registers for a timestamp, `None` outside of 2000-2099
+fn registers_from_unix(timestamp: u32) -> Option<[u8; 7]> {
+    if !(UNIX_2000..=UNIX_2099_END).contains(&timestamp) {
+        return None;
+    }
+
+    let seconds = timestamp - UNIX_2000;
+    let mut days = seconds / SECONDS_PER_DAY;
+    let seconds_of_day = seconds % SECONDS_PER_DAY;
+    let weekday = (days + WEEKDAY_2000) % 7;
+
+    // Every 4 years (starting with a leap year) span 1461 days
+    let mut year = days / 1461 * 4;
+    days %= 1461;
+    while days >= days_before_year(year + 1) - days_before_year(year) {
+        days -= days_before_year(year + 1) - days_before_year(year);
+        year += 1;
+    }
+
+    let mut month = 1;
+    while days >= days_in_month(CENTURY + year as u16, month) as u32 {
+        days -= days_in_month(CENTURY + year as u16, month) as u32;
+        month += 1;
+    }
+
+    Some([
+        bin2bcd((seconds_of_day % 60) as u8),
+        bin2bcd((seconds_of_day / 60 % 60) as u8),
+        bin2bcd((seconds_of_day / 3600) as u8),
+        1 << weekday,
+        bin2bcd(days as u8 + 1),
+        bin2bcd(month),
+        bin2bcd(year as u8),
+    ])
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Reads the time as seconds since the Unix epoch (the RTC is assumed to
+    /// keep UTC). Same failure modes as `get_time`.
+    pub fn get_unix_timestamp(&mut self) -> Result<u32, Error<E>> {
+        let date_time = self.get_time()?;
+        // `get_time` only decodes 2000-2099
+        unix_from_date_time(&date_time).ok_or(Error::InvalidRegister)
+    }
+
+    /// Sets the time from seconds since the Unix epoch; only 2000-2099
+    /// (946684800-4102444799) can be represented.
+    pub fn set_unix_timestamp(&mut self, timestamp: u32) -> Result<(), Error<E>> {
+        let time_registers = registers_from_unix(timestamp).ok_or(Error::UnsupportedDate)?;
+        self.write_time_registers(&time_registers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_time;
+    use crate::sim::testing::running_at;
+
+    #[test]
+    fn registers_from_unix_known_values() {
+        assert_eq!(
+            registers_from_unix(UNIX_2000),
+            Some([0x00, 0x00, 0x00, 0x40, 0x01, 0x01, 0x00])
+        );
+        // 2024-02-29T12:34:56Z, a Thursday
+        assert_eq!(
+            registers_from_unix(1_709_210_096),
+            Some([0x56, 0x34, 0x12, 0x10, 0x29, 0x02, 0x24])
+        );
+        assert_eq!(
+            registers_from_unix(UNIX_2099_END),
+            Some([0x59, 0x59, 0x23, 0x10, 0x31, 0x12, 0x99])
+        );
+    }
+
+    #[test]
+    fn registers_from_unix_out_of_range() {
+        assert_eq!(registers_from_unix(UNIX_2000 - 1), None);
+        assert_eq!(registers_from_unix(UNIX_2099_END + 1), None);
+    }
+
+    #[test]
+    fn unix_from_date_time_out_of_range() {
+        let date_time = DateTime::new(1999, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(unix_from_date_time(&date_time), None);
+        let date_time = DateTime::new(2100, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(unix_from_date_time(&date_time), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        // Odd step, to hit every time of day and day of month
+        for timestamp in (UNIX_2000..=UNIX_2099_END).step_by(86_399 * 7 + 12_345) {
+            let registers = registers_from_unix(timestamp).unwrap();
+            let date_time = decode_time(&registers).unwrap();
+            assert_eq!(1 << date_time.weekday().number_from_sunday(), registers[3]);
+            assert_eq!(unix_from_date_time(&date_time), Some(timestamp));
+        }
+    }
+
+    #[test]
+    fn set_get_unix_timestamp() {
+        let mut sim = running_at(DateTime::new(2024, 1, 1, 0, 0, 0).unwrap());
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_unix_timestamp(1_709_210_096).unwrap();
+        assert_eq!(rtc.get_unix_timestamp(), Ok(1_709_210_096));
+        assert_eq!(
+            rtc.get_time(),
+            Ok(DateTime::new(2024, 2, 29, 12, 34, 56).unwrap())
+        );
+        assert_eq!(
+            rtc.set_unix_timestamp(UNIX_2099_END + 1),
+            Err(Error::UnsupportedDate)
+        );
+    }
+}
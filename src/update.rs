@@ -0,0 +1,132 @@
+use embedded_hal::i2c::I2c;
+
+use crate::registers::{
+    BIT_REGISTER_CONTROL_STOP, BIT_REGISTER_CONTROL_UIE, BIT_REGISTER_EXTENSION_USEL,
+    BIT_REGISTER_FLAG_UF, REGISTER_CONTROL, REGISTER_EXTENSION, REGISTER_FLAG,
+};
+use crate::{Error, Rx8010sj};
+
+/// Period of the time update interrupt (USEL bit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateInterval {
+    Second,
+    Minute,
+}
+
+impl UpdateInterval {
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            UpdateInterval::Second => 0,
+            UpdateInterval::Minute => BIT_REGISTER_EXTENSION_USEL,
+        }
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        if (bits & BIT_REGISTER_EXTENSION_USEL) > 0 {
+            UpdateInterval::Minute
+        } else {
+            UpdateInterval::Second
+        }
+    }
+}
+
+impl<I2C, E> Rx8010sj<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    pub fn set_update_interval(&mut self, interval: UpdateInterval) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_EXTENSION,
+            BIT_REGISTER_EXTENSION_USEL,
+            interval.bits(),
+        )
+    }
+
+    pub fn get_update_interval(&mut self) -> Result<UpdateInterval, Error<E>> {
+        let extension_register = self.read_register(REGISTER_EXTENSION)?;
+        Ok(UpdateInterval::from_bits(extension_register))
+    }
+
+    /// Enables the time update interrupt (UIE): /IRQ pulses low on every update.
+    pub fn enable_update_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(
+            REGISTER_CONTROL,
+            BIT_REGISTER_CONTROL_UIE,
+            BIT_REGISTER_CONTROL_UIE,
+        )
+    }
+
+    pub fn disable_update_interrupt(&mut self) -> Result<(), Error<E>> {
+        self.modify_register(REGISTER_CONTROL, BIT_REGISTER_CONTROL_UIE, 0)
+    }
+
+    /// Reads the update flag (UF), raised on every update even if UIE is off
+    pub fn is_update_pending(&mut self) -> Result<bool, Error<E>> {
+        let flag_register = self.read_register(REGISTER_FLAG)?;
+        Ok((flag_register & BIT_REGISTER_FLAG_UF) > 0)
+    }
+
+    /// Clears the update flag
+    pub fn clear_update_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flags(BIT_REGISTER_FLAG_UF)
+    }
+
+    /// Busy-polls UF and returns right after the next update, leaving UF
+    /// cleared. Fails with `ClockStopped` if STOP is set, as no update would
+    /// ever come, and with `InvalidArgument` if the update interval is not
+    /// `UpdateInterval::Second`.
+    pub fn wait_for_next_second(&mut self) -> Result<(), Error<E>> {
+        let extension_register = self.read_register(REGISTER_EXTENSION)?;
+        if UpdateInterval::from_bits(extension_register) != UpdateInterval::Second {
+            return Err(Error::InvalidArgument);
+        }
+        let control_register = self.read_register(REGISTER_CONTROL)?;
+        if (control_register & BIT_REGISTER_CONTROL_STOP) > 0 {
+            return Err(Error::ClockStopped);
+        }
+
+        self.clear_update_flag()?;
+        while !self.is_update_pending()? {}
+        self.clear_update_flag()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::sim::testing::{date_time, running_at};
+
+    #[test]
+    fn update_raises_flag() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_update_interval(UpdateInterval::Minute).unwrap();
+        assert_eq!(rtc.get_update_interval(), Ok(UpdateInterval::Minute));
+        rtc.clear_update_flag().unwrap();
+
+        sim.advance(Duration::from_secs(59));
+        assert_eq!(Rx8010sj::new(&mut sim).is_update_pending(), Ok(false));
+        sim.advance(Duration::from_secs(1));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        assert_eq!(rtc.is_update_pending(), Ok(true));
+
+        rtc.set_update_interval(UpdateInterval::Second).unwrap();
+        rtc.clear_update_flag().unwrap();
+        sim.advance(Duration::from_secs(1));
+        assert_eq!(Rx8010sj::new(&mut sim).is_update_pending(), Ok(true));
+    }
+
+    #[test]
+    fn wait_for_next_second_rejects_unreachable_updates() {
+        let mut sim = running_at(date_time(2024, 3, 1, 7, 0, 0));
+        let mut rtc = Rx8010sj::new(&mut sim);
+        rtc.set_update_interval(UpdateInterval::Minute).unwrap();
+        assert_eq!(rtc.wait_for_next_second(), Err(Error::InvalidArgument));
+
+        rtc.set_update_interval(UpdateInterval::Second).unwrap();
+        rtc.set_stopped(true).unwrap();
+        assert_eq!(rtc.wait_for_next_second(), Err(Error::ClockStopped));
+    }
+}